@@ -1,4 +1,9 @@
 fn main() {
+    // The C shim wraps the x86 CPUID instruction and is not needed elsewhere
+    let arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    if arch != "x86" && arch != "x86_64" {
+        return;
+    }
     cc::Build::new()
         .file("src/c/cpuid.c")
         .include("src/c")
@@ -1,162 +1,804 @@
 #![allow(dead_code)]
+use crate::cache::{self, CacheInfo};
+use crate::cpufreq::{self, CpuFreq};
+use crate::sysfs::{self, ProcCpuEntry, Source};
+use crate::{numa, topology};
+use bitflags::bitflags;
 use core::fmt;
-#[cfg(target_os = "macos")]
-use libc::{c_void, sysctlbyname};
-#[cfg(target_os = "linux")]
-use libc::{
-    getauxval, sysconf, AT_HWCAP, AT_HWCAP2, HWCAP_AES, HWCAP_CRC32, HWCAP_NEON, HWCAP_PMULL,
-    HWCAP_SHA1, HWCAP_SHA2, _SC_NPROCESSORS_ONLN,
-};
-#[cfg(windows)]
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+use libc::AT_HWCAP3;
+#[cfg(all(target_os = "macos", any(target_arch = "arm", target_arch = "aarch64")))]
+use libc::{c_void, sysconf, sysctlbyname, _SC_NPROCESSORS_ONLN};
+#[cfg(all(target_os = "linux", any(target_arch = "arm", target_arch = "aarch64")))]
+use libc::{getauxval, AT_HWCAP, AT_HWCAP2};
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use once_cell::sync::Lazy;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use std::sync::Arc;
+#[cfg(all(windows, any(target_arch = "arm", target_arch = "aarch64")))]
 use winapi::um::sysinfoapi::GetNativeSystemInfo;
 
+/// CTR_EL0 and DCZID_EL0 decoding
+pub mod cacheregs;
+/// aarch64 feature ID register decoding
+pub mod idregs;
+/// Main ID Register decoding and the implementer/part database
+pub mod midr;
+/// SVE and SME vector length queries
+pub mod vector;
+/// Armv8.x / Armv9.x version inference
+pub mod version;
+
+use cacheregs::{Ctr, Dczid};
+use idregs::IdRegisters;
+use midr::Midr;
+use vector::VectorLengths;
+
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     /// Packed feature flags for ARM/ARM64.
     /// Each flag represents a CPU feature detected at runtime.
-    pub struct ArmFeatures: u64 {
+    /// Names follow the Linux hwcap names, except that `ASIMD` is called `NEON`.
+    /// Flags marked 32-bit are only reported by 32-bit ARM kernels.
+    pub struct ArmFeatures: u128 {
         /// NEON SIMD instructions
-        const NEON  = 1 << 0;
+        const NEON = 1 << 0;
         /// AES instructions
-        const AES   = 1 << 1;
+        const AES = 1 << 1;
         /// Polynomial multiplication (PMULL)
         const PMULL = 1 << 2;
         /// SHA1 instructions
-        const SHA1  = 1 << 3;
+        const SHA1 = 1 << 3;
         /// SHA2 instructions
-        const SHA2  = 1 << 4;
+        const SHA2 = 1 << 4;
         /// CRC32 instructions
         const CRC32 = 1 << 5;
+        /// Floating point
+        const FP = 1 << 6;
+        /// Generic timer event stream
+        const EVTSTRM = 1 << 7;
+        /// Large System Extensions atomics (LSE)
+        const ATOMICS = 1 << 8;
+        /// Half-precision floating point
+        const FPHP = 1 << 9;
+        /// Half-precision Advanced SIMD
+        const ASIMDHP = 1 << 10;
+        /// EL0 access to ID registers via MRS emulation
+        const CPUID = 1 << 11;
+        /// Rounding double multiply accumulate (RDM)
+        const ASIMDRDM = 1 << 12;
+        /// JavaScript conversion (FJCVTZS)
+        const JSCVT = 1 << 13;
+        /// Complex number multiply-add
+        const FCMA = 1 << 14;
+        /// Load-acquire RCpc (LDAPR)
+        const LRCPC = 1 << 15;
+        /// Data cache clean to point of persistence
+        const DCPOP = 1 << 16;
+        /// SHA3 instructions
+        const SHA3 = 1 << 17;
+        /// SM3 instructions
+        const SM3 = 1 << 18;
+        /// SM4 instructions
+        const SM4 = 1 << 19;
+        /// Dot product
+        const ASIMDDP = 1 << 20;
+        /// SHA512 instructions
+        const SHA512 = 1 << 21;
+        /// Scalable Vector Extension
+        const SVE = 1 << 22;
+        /// FP16 multiply-add long (FHM)
+        const ASIMDFHM = 1 << 23;
+        /// Data independent timing
+        const DIT = 1 << 24;
+        /// Unaligned single-copy atomicity
+        const USCAT = 1 << 25;
+        /// Immediate-offset LDAPR/STLR (LRCPC2)
+        const ILRCPC = 1 << 26;
+        /// Flag manipulation
+        const FLAGM = 1 << 27;
+        /// Speculative store bypass safe
+        const SSBS = 1 << 28;
+        /// Speculation barrier
+        const SB = 1 << 29;
+        /// Pointer authentication (address)
+        const PACA = 1 << 30;
+        /// Pointer authentication (generic)
+        const PACG = 1 << 31;
+        /// Guarded control stack
+        const GCS = 1 << 32;
+        /// Compare and branch
+        const CMPBR = 1 << 33;
+        /// FP to integer conversion in SIMD registers
+        const FPRCVT = 1 << 34;
+        /// FP8 to FP16 matrix multiply
+        const F8MM8 = 1 << 35;
+        /// FP8 to FP32 matrix multiply
+        const F8MM4 = 1 << 36;
+        /// SVE FP16 matrix multiply
+        const SVE_F16MM = 1 << 37;
+        /// SVE element permute
+        const SVE_ELTPERM = 1 << 38;
+        /// SVE multi-vector AES
+        const SVE_AES2 = 1 << 39;
+        /// SVE BFloat16 scale
+        const SVE_BFSCALE = 1 << 40;
+        /// SVE2.2
+        const SVE2P2 = 1 << 41;
+        /// SME2.2
+        const SME2P2 = 1 << 42;
+        /// SME bit permute
+        const SME_SBITPERM = 1 << 43;
+        /// SME AES
+        const SME_AES = 1 << 44;
+        /// SME FEXPA
+        const SME_SFEXPA = 1 << 45;
+        /// SME structured outer product
+        const SME_STMOP = 1 << 46;
+        /// SME quarter-tile outer product
+        const SME_SMOP4 = 1 << 47;
+        /// Data cache clean to point of deep persistence
+        const DCPODP = 1 << 48;
+        /// SVE2
+        const SVE2 = 1 << 49;
+        /// SVE2 AES
+        const SVEAES = 1 << 50;
+        /// SVE2 128-bit polynomial multiply
+        const SVEPMULL = 1 << 51;
+        /// SVE2 bit permute
+        const SVEBITPERM = 1 << 52;
+        /// SVE2 SHA3
+        const SVESHA3 = 1 << 53;
+        /// SVE2 SM4
+        const SVESM4 = 1 << 54;
+        /// Flag manipulation v2
+        const FLAGM2 = 1 << 55;
+        /// Floating point to integer rounding
+        const FRINT = 1 << 56;
+        /// SVE Int8 matrix multiply
+        const SVEI8MM = 1 << 57;
+        /// SVE FP32 matrix multiply
+        const SVEF32MM = 1 << 58;
+        /// SVE FP64 matrix multiply
+        const SVEF64MM = 1 << 59;
+        /// SVE BFloat16
+        const SVEBF16 = 1 << 60;
+        /// Int8 matrix multiply
+        const I8MM = 1 << 61;
+        /// BFloat16
+        const BF16 = 1 << 62;
+        /// Data gathering hint
+        const DGH = 1 << 63;
+        /// Random number generation (RNDR)
+        const RNG = 1 << 64;
+        /// Branch target identification
+        const BTI = 1 << 65;
+        /// Memory tagging
+        const MTE = 1 << 66;
+        /// Enhanced counter virtualization
+        const ECV = 1 << 67;
+        /// Alternate floating-point behaviour
+        const AFP = 1 << 68;
+        /// Increased precision of reciprocal estimates
+        const RPRES = 1 << 69;
+        /// Memory tagging with asymmetric faults
+        const MTE3 = 1 << 70;
+        /// Scalable Matrix Extension
+        const SME = 1 << 71;
+        /// SME Int16 to Int64 outer product
+        const SME_I16I64 = 1 << 72;
+        /// SME FP64 outer product
+        const SME_F64F64 = 1 << 73;
+        /// SME Int8 to Int32 outer product
+        const SME_I8I32 = 1 << 74;
+        /// SME FP16 to FP32 outer product
+        const SME_F16F32 = 1 << 75;
+        /// SME BFloat16 to FP32 outer product
+        const SME_B16F32 = 1 << 76;
+        /// SME FP32 outer product
+        const SME_F32F32 = 1 << 77;
+        /// SME full A64 in streaming mode
+        const SME_FA64 = 1 << 78;
+        /// WFE/WFI with timeout
+        const WFXT = 1 << 79;
+        /// Extended BFloat16 behaviour
+        const EBF16 = 1 << 80;
+        /// SVE extended BFloat16 behaviour
+        const SVE_EBF16 = 1 << 81;
+        /// Common short sequence compression
+        const CSSC = 1 << 82;
+        /// Range prefetch
+        const RPRFM = 1 << 83;
+        /// SVE2.1
+        const SVE2P1 = 1 << 84;
+        /// SME2
+        const SME2 = 1 << 85;
+        /// SME2.1
+        const SME2P1 = 1 << 86;
+        /// SME2 Int16 to Int32 outer product
+        const SME_I16I32 = 1 << 87;
+        /// SME2 binary Int32 outer product
+        const SME_BI32I32 = 1 << 88;
+        /// SME2.1 BFloat16 arithmetic
+        const SME_B16B16 = 1 << 89;
+        /// SME2.1 FP16 arithmetic
+        const SME_F16F16 = 1 << 90;
+        /// Memory copy and set instructions
+        const MOPS = 1 << 91;
+        /// Hinted conditional branches
+        const HBC = 1 << 92;
+        /// SVE BFloat16 arithmetic
+        const SVE_B16B16 = 1 << 93;
+        /// Load-acquire RCpc v3
+        const LRCPC3 = 1 << 94;
+        /// 128-bit atomics
+        const LSE128 = 1 << 95;
+        /// Floating-point mode register
+        const FPMR = 1 << 96;
+        /// Lookup table instructions
+        const LUT = 1 << 97;
+        /// Floating-point absolute min/max
+        const FAMINMAX = 1 << 98;
+        /// FP8 conversion
+        const F8CVT = 1 << 99;
+        /// FP8 multiply-add
+        const F8FMA = 1 << 100;
+        /// FP8 four-way dot product
+        const F8DP4 = 1 << 101;
+        /// FP8 two-way dot product
+        const F8DP2 = 1 << 102;
+        /// FP8 E4M3 format
+        const F8E4M3 = 1 << 103;
+        /// FP8 E5M2 format
+        const F8E5M2 = 1 << 104;
+        /// SME lookup table v2
+        const SME_LUTV2 = 1 << 105;
+        /// SME FP8 to FP16 outer product
+        const SME_F8F16 = 1 << 106;
+        /// SME FP8 to FP32 outer product
+        const SME_F8F32 = 1 << 107;
+        /// SME FP8 multiply-add
+        const SME_SF8FMA = 1 << 108;
+        /// SME FP8 four-way dot product
+        const SME_SF8DP4 = 1 << 109;
+        /// SME FP8 two-way dot product
+        const SME_SF8DP2 = 1 << 110;
+        /// Permission overlay extension
+        const POE = 1 << 111;
+        /// MTE fault address reporting
+        const MTE_FAR = 1 << 112;
+        /// MTE store-only tag checking
+        const MTE_STORE_ONLY = 1 << 113;
+        /// SWP/SWPB instructions (32-bit)
+        const SWP = 1 << 114;
+        /// Half-word loads and stores (32-bit)
+        const HALF = 1 << 115;
+        /// Thumb instruction set (32-bit)
+        const THUMB = 1 << 116;
+        /// Fast 32x32->64 multiply (32-bit)
+        const FASTMULT = 1 << 117;
+        /// VFP floating point (32-bit)
+        const VFP = 1 << 118;
+        /// VFPv3 (32-bit)
+        const VFPV3 = 1 << 119;
+        /// VFPv3 or VFPv4 with 16 double registers (32-bit)
+        const VFPV3D16 = 1 << 120;
+        /// VFPv4 (32-bit)
+        const VFPV4 = 1 << 121;
+        /// Integer divide in ARM state (32-bit)
+        const IDIVA = 1 << 122;
+        /// Integer divide in Thumb state (32-bit)
+        const IDIVT = 1 << 123;
+        /// VFP with 32 double registers (32-bit)
+        const VFPD32 = 1 << 124;
+        /// Large Physical Address Extension (32-bit)
+        const LPAE = 1 << 125;
+    }
+}
+
+/// Helper macro for hwcap bit checks
+macro_rules! hwcap_flags {
+    ($flags:ident, $reg:ident, $($bit:expr => $flag:ident),+ $(,)?) => {
+        $( if $reg & (1u64 << $bit) != 0 { $flags.insert(ArmFeatures::$flag); } )+
+    };
+}
+
+impl ArmFeatures {
+    /// Decodes the Linux aarch64 `AT_HWCAP`, `AT_HWCAP2` and `AT_HWCAP3` auxiliary vector values.
+    pub fn from_hwcaps(hwcap: u64, hwcap2: u64, hwcap3: u64) -> ArmFeatures {
+        let mut f = ArmFeatures::empty();
+        hwcap_flags!(f, hwcap,
+            0=>FP,1=>NEON,2=>EVTSTRM,3=>AES,4=>PMULL,5=>SHA1,6=>SHA2,7=>CRC32,8=>ATOMICS,
+            9=>FPHP,10=>ASIMDHP,11=>CPUID,12=>ASIMDRDM,13=>JSCVT,14=>FCMA,15=>LRCPC,
+            16=>DCPOP,17=>SHA3,18=>SM3,19=>SM4,20=>ASIMDDP,21=>SHA512,22=>SVE,23=>ASIMDFHM,
+            24=>DIT,25=>USCAT,26=>ILRCPC,27=>FLAGM,28=>SSBS,29=>SB,30=>PACA,31=>PACG,
+            32=>GCS,33=>CMPBR,34=>FPRCVT,35=>F8MM8,36=>F8MM4,37=>SVE_F16MM,38=>SVE_ELTPERM,
+            39=>SVE_AES2,40=>SVE_BFSCALE,41=>SVE2P2,42=>SME2P2,43=>SME_SBITPERM,
+            44=>SME_AES,45=>SME_SFEXPA,46=>SME_STMOP,47=>SME_SMOP4,
+        );
+        hwcap_flags!(f, hwcap2,
+            0=>DCPODP,1=>SVE2,2=>SVEAES,3=>SVEPMULL,4=>SVEBITPERM,5=>SVESHA3,6=>SVESM4,
+            7=>FLAGM2,8=>FRINT,9=>SVEI8MM,10=>SVEF32MM,11=>SVEF64MM,12=>SVEBF16,13=>I8MM,
+            14=>BF16,15=>DGH,16=>RNG,17=>BTI,18=>MTE,19=>ECV,20=>AFP,21=>RPRES,22=>MTE3,
+            23=>SME,24=>SME_I16I64,25=>SME_F64F64,26=>SME_I8I32,27=>SME_F16F32,
+            28=>SME_B16F32,29=>SME_F32F32,30=>SME_FA64,31=>WFXT,32=>EBF16,33=>SVE_EBF16,
+            34=>CSSC,35=>RPRFM,36=>SVE2P1,37=>SME2,38=>SME2P1,39=>SME_I16I32,
+            40=>SME_BI32I32,41=>SME_B16B16,42=>SME_F16F16,43=>MOPS,44=>HBC,45=>SVE_B16B16,
+            46=>LRCPC3,47=>LSE128,48=>FPMR,49=>LUT,50=>FAMINMAX,51=>F8CVT,52=>F8FMA,
+            53=>F8DP4,54=>F8DP2,55=>F8E4M3,56=>F8E5M2,57=>SME_LUTV2,58=>SME_F8F16,
+            59=>SME_F8F32,60=>SME_SF8FMA,61=>SME_SF8DP4,62=>SME_SF8DP2,63=>POE,
+        );
+        hwcap_flags!(f, hwcap3,
+            0=>MTE_FAR,1=>MTE_STORE_ONLY,
+        );
+        f
+    }
+
+    /// Decodes the 32-bit ARM Linux `AT_HWCAP` and `AT_HWCAP2` values.
+    /// The bit layout differs from aarch64: crypto and CRC32 live in `AT_HWCAP2`.
+    /// `ASIMDBF16` is reported as [`ArmFeatures::BF16`].
+    pub fn from_hwcaps32(hwcap: u32, hwcap2: u32) -> ArmFeatures {
+        let (hwcap, hwcap2) = (hwcap as u64, hwcap2 as u64);
+        let mut f = ArmFeatures::empty();
+        hwcap_flags!(f, hwcap,
+            0=>SWP,1=>HALF,2=>THUMB,4=>FASTMULT,6=>VFP,12=>NEON,13=>VFPV3,14=>VFPV3D16,
+            16=>VFPV4,17=>IDIVA,18=>IDIVT,19=>VFPD32,20=>LPAE,21=>EVTSTRM,22=>FPHP,
+            23=>ASIMDHP,24=>ASIMDDP,25=>ASIMDFHM,26=>BF16,27=>I8MM,
+        );
+        hwcap_flags!(f, hwcap2,
+            0=>AES,1=>PMULL,2=>SHA1,3=>SHA2,4=>CRC32,5=>SB,6=>SSBS,
+        );
+        f
+    }
+
+    /// Parses the `Features` line of `/proc/cpuinfo`, using the kernel's flag names
+    /// (the flag names in lower case without underscores, `asimd`/`neon` for
+    /// [`ArmFeatures::NEON`] and 32-bit `asimdbf16` for [`ArmFeatures::BF16`]).
+    /// Names without a matching flag are ignored.
+    pub fn from_cpuinfo_features(line: &str) -> ArmFeatures {
+        let mut f = ArmFeatures::empty();
+        for token in line.split_whitespace() {
+            match token {
+                "asimd" | "neon" => f.insert(ArmFeatures::NEON),
+                "asimdbf16" => f.insert(ArmFeatures::BF16),
+                _ => {
+                    if let Some((_, flag)) = ArmFeatures::all()
+                        .iter_names()
+                        .find(|(name, _)| kernel_name_eq(name, token))
+                    {
+                        f.insert(flag);
+                    }
+                }
+            }
+        }
+        f
+    }
+}
+
+/// Compares a flag name such as `SME_I16I64` with a kernel name such as `smei16i64`.
+fn kernel_name_eq(flag: &str, kernel: &str) -> bool {
+    flag.bytes()
+        .filter(|&b| b != b'_')
+        .map(|b| b.to_ascii_lowercase())
+        .eq(kernel.bytes())
+}
+
+/// Disagreement between the auxiliary vector and the `/proc/cpuinfo` `Features` line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeatureMismatch {
+    /// Reported by the auxiliary vector but missing from `/proc/cpuinfo`
+    pub hwcaps_only: ArmFeatures,
+    /// Listed in `/proc/cpuinfo` but missing from the auxiliary vector
+    pub cpuinfo_only: ArmFeatures,
+}
+
+impl FeatureMismatch {
+    /// Compares the two feature sources. Returns `None` if they agree or if
+    /// either one is empty, meaning that source was unavailable.
+    pub fn between(hwcaps: ArmFeatures, cpuinfo: ArmFeatures) -> Option<FeatureMismatch> {
+        if hwcaps.is_empty() || cpuinfo.is_empty() || hwcaps == cpuinfo {
+            return None;
+        }
+        Some(FeatureMismatch {
+            hwcaps_only: hwcaps - cpuinfo,
+            cpuinfo_only: cpuinfo - hwcaps,
+        })
+    }
+}
+
+/// Relative performance class of a core on a heterogeneous (big.LITTLE / DynamIQ) system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreClass {
+    /// Highest-capacity cores
+    Big,
+    /// Cores between the biggest and the smallest ones
+    Mid,
+    /// Lowest-capacity cores
+    Little,
+}
+
+impl CoreClass {
+    /// Classifies a `cpu_capacity` value against the capacities of every CPU in the system.
+    /// Returns `None` if all CPUs have the same capacity.
+    pub fn from_capacity(capacity: u32, all: &[u32]) -> Option<CoreClass> {
+        let (min, max) = (*all.iter().min()?, *all.iter().max()?);
+        if min == max {
+            None
+        } else if capacity >= max {
+            Some(CoreClass::Big)
+        } else if capacity <= min {
+            Some(CoreClass::Little)
+        } else {
+            Some(CoreClass::Mid)
+        }
     }
 }
 
 /// Stores information about a single logical ARM/ARM64 CPU.
 /// Includes vendor, brand string, feature flags, core/thread counts.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ArmCpuInfo {
+    /// OS logical CPU ID this info describes
+    pub cpu_id: usize,
     /// CPU vendor string (e.g., "ARM", "Apple")
     pub vendor: String,
     /// CPU brand string (e.g., "Apple M1", "Cortex-A72")
     pub brand: String,
     /// Feature flags detected at runtime
     pub features: ArmFeatures,
+    /// Where the auxiliary vector and `/proc/cpuinfo` disagree (Linux only)
+    pub feature_mismatch: Option<FeatureMismatch>,
     /// Number of physical cores
     pub cores: u32,
     /// Number of threads per core (usually 1 for ARM)
     pub threads_per_core: u32,
+    /// Cache hierarchy of the CPU this was gathered on, ordered by level
+    pub caches: Vec<CacheInfo>,
+    /// Main ID Register of the CPU this was gathered on, if the OS exposes it
+    pub midr: Option<Midr>,
+    /// Raw feature ID registers, if the kernel allows reading them (Linux aarch64 only)
+    pub id_registers: Option<IdRegisters>,
+    /// Scheduler capacity from sysfs `cpu_capacity` (1024 = the fastest core)
+    pub cpu_capacity: Option<u32>,
+    /// Cluster ID from sysfs topology, `-1` if unknown
+    pub cluster_id: i32,
+    /// NUMA node the CPU belongs to, if the OS reports one
+    pub numa_node: Option<usize>,
+    /// Core class on heterogeneous systems, `None` if all cores are alike
+    pub core_class: Option<CoreClass>,
+    /// SVE vector lengths, if SVE is usable (Linux aarch64 only)
+    pub sve_lengths: Option<VectorLengths>,
+    /// SME streaming vector lengths, if SME is usable (Linux aarch64 only)
+    pub sme_lengths: Option<VectorLengths>,
+    /// Decoded CTR_EL0 (cache line sizes and coherence), aarch64 only
+    pub ctr: Option<Ctr>,
+    /// Decoded DCZID_EL0 (`DC ZVA` block size), aarch64 only
+    pub dczid: Option<Dczid>,
 }
 
-/// Gathers ARM/ARM64 CPU information for the current system.
-/// Detects vendor, brand, features, and topology (core/thread count).
-/// Uses OS-specific APIs and `/proc/cpuinfo` where available.
-pub fn gather() -> ArmCpuInfo {
-    // Vendor & brand
-    let (vendor, brand) = {
-        #[cfg(target_os = "linux")]
-        {
-            let info = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
-            let mut vendor = String::new();
-            let mut brand = String::new();
-            for line in info.lines() {
-                if line.starts_with("Hardware") {
-                    if let Some(val) = line.split(':').nth(1) {
-                        vendor = val.trim().to_string();
-                    }
-                } else if line.starts_with("model name") || line.starts_with("Processor") {
-                    if let Some(val) = line.split(':').nth(1) {
-                        brand = val.trim().to_string();
-                    }
-                }
-            }
-            (vendor, brand)
-        }
-        #[cfg(target_os = "macos")]
-        {
-            let mut buf = [0u8; 64];
-            let mut len = buf.len();
-            unsafe {
-                sysctlbyname(
-                    b"machdep.cpu.brand_string\0".as_ptr() as *const i8,
-                    buf.as_mut_ptr() as *mut c_void,
-                    &mut len,
-                    std::ptr::null_mut(),
-                    0,
-                );
-            }
-            let vendor = "Apple".to_string();
-            let brand = String::from_utf8_lossy(&buf[..len]).trim().to_string();
-            (vendor, brand)
-        }
-        #[cfg(windows)]
-        {
-            ("Unknown".to_string(), "ARM CPU".to_string())
-        }
-        #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
-        {
-            ("Unknown".to_string(), "ARM CPU".to_string())
-        }
+impl ArmCpuInfo {
+    /// Infers the Armv8.x/Armv9.x version from the aarch64 feature flags.
+    /// Meaningless for 32-bit feature sets, which never satisfy Armv8.0.
+    pub fn arch_version(&self) -> version::VersionReport {
+        version::infer(self.features)
+    }
+
+    /// Reads the current cpufreq state of this CPU from the live system, even if
+    /// this info was built from a captured tree; use [`Self::frequency_from`] for those.
+    /// Not cached, since the current frequency and governor change at runtime.
+    pub fn frequency(&self) -> Option<CpuFreq> {
+        cpufreq::read_cpu(self.cpu_id)
+    }
+
+    /// Reads the cpufreq state of this CPU from `src`.
+    pub fn frequency_from(&self, src: &Source) -> Option<CpuFreq> {
+        cpufreq::read_cpu_from(src, self.cpu_id)
+    }
+}
+
+/// Extracts (board name, model name) for CPU `cpu_id` from parsed `/proc/cpuinfo`
+/// entries. The CPU's own block wins; kernels that print a field only once
+/// (such as the trailing `Hardware` line) fall back to the last one seen.
+fn parse_vendor_brand(entries: &[ProcCpuEntry], cpu_id: usize) -> (String, String) {
+    let own = entries.iter().find(|e| e.processor == Some(cpu_id));
+    let field = |keys: &[&str]| {
+        own.and_then(|e| keys.iter().find_map(|k| e.get(k)))
+            .or_else(|| {
+                entries
+                    .iter()
+                    .flat_map(|e| &e.fields)
+                    .rev()
+                    .find(|(k, _)| keys.contains(&k.as_str()))
+                    .map(|(_, v)| v.as_str())
+            })
+            .unwrap_or_default()
+            .to_string()
     };
+    (field(&["Hardware"]), field(&["model name", "Processor"]))
+}
 
-    // Features
-    let mut f = ArmFeatures::empty();
-    #[cfg(target_os = "linux")]
-    unsafe {
-        let caps = getauxval(AT_HWCAP) as u64;
-        let caps2 = getauxval(AT_HWCAP2) as u64;
-        if caps & (HWCAP_NEON as u64) != 0 {
-            f.insert(ArmFeatures::NEON);
-        }
-        if caps & (HWCAP_AES as u64) != 0 {
-            f.insert(ArmFeatures::AES);
-        }
-        if caps & (HWCAP_PMULL as u64) != 0 {
-            f.insert(ArmFeatures::PMULL);
-        }
-        if caps & (HWCAP_SHA1 as u64) != 0 {
-            f.insert(ArmFeatures::SHA1);
-        }
-        if caps & (HWCAP_SHA2 as u64) != 0 {
-            f.insert(ArmFeatures::SHA2);
-        }
-        if caps & (HWCAP_CRC32 as u64) != 0 {
-            f.insert(ArmFeatures::CRC32);
+/// Reads the scheduler capacity (1024 = the fastest core) of a logical CPU.
+fn read_capacity(src: &Source, cpu_id: usize) -> Option<u32> {
+    src.read_trimmed(&format!("{}/cpu{}/cpu_capacity", sysfs::CPU_ROOT, cpu_id))?
+        .parse()
+        .ok()
+}
+
+/// System-wide data shared by every per-CPU record, read once per gather.
+struct SystemView {
+    entries: Vec<ProcCpuEntry>,
+    topology: Option<topology::Topology>,
+    numa: Option<numa::NumaTopology>,
+    cpu_ids: Vec<usize>,
+    capacities: Vec<u32>,
+}
+
+impl SystemView {
+    fn read(src: &Source) -> SystemView {
+        let entries = src.proc_cpuinfo();
+        let cpu_ids = src
+            .online_cpus()
+            .unwrap_or_else(|| entries.iter().filter_map(|e| e.processor).collect());
+        let capacities = cpu_ids
+            .iter()
+            .filter_map(|&cpu| read_capacity(src, cpu))
+            .collect();
+        SystemView {
+            entries,
+            topology: topology::read_from(src),
+            numa: numa::read_from(src),
+            cpu_ids,
+            capacities,
         }
     }
-    #[cfg(target_os = "macos")]
-    {
-        // macOS feature detection omitted for brevity
+
+    fn cpu(&self, src: &Source, cpu_id: usize) -> ArmCpuInfo {
+        let midr = midr::read_cpu_with_entries(src, cpu_id, &self.entries);
+        // The MIDR names the core itself; the cpuinfo strings are only a fallback,
+        // since `Hardware` is a board name and `model name` is often generic or absent
+        let (hardware, model) = parse_vendor_brand(&self.entries, cpu_id);
+        let vendor = midr
+            .and_then(|m| m.vendor_name())
+            .map_or(hardware, str::to_string);
+        let brand = midr
+            .and_then(|m| m.part_name())
+            .map_or(model, str::to_string);
+
+        // Topology: prefer sysfs, which knows about SMT and multiple sockets
+        let (cores, threads_per_core) = match &self.topology {
+            Some(topo) => (topo.core_count() as u32, topo.threads_per_core() as u32),
+            None => (self.cpu_ids.len().max(1) as u32, 1),
+        };
+        let cpu_capacity = read_capacity(src, cpu_id);
+        // Prefer this CPU's block; 32-bit kernels may print a single shared block
+        let features = self
+            .entries
+            .iter()
+            .find(|e| e.processor == Some(cpu_id) && e.get("Features").is_some())
+            .or_else(|| self.entries.iter().find(|e| e.get("Features").is_some()))
+            .and_then(|e| e.get("Features"))
+            .map_or(ArmFeatures::empty(), ArmFeatures::from_cpuinfo_features);
+
+        ArmCpuInfo {
+            cpu_id,
+            vendor,
+            brand,
+            features,
+            feature_mismatch: None,
+            cores,
+            threads_per_core,
+            caches: cache::read_cpu_from(src, cpu_id),
+            midr,
+            id_registers: None,
+            cpu_capacity,
+            cluster_id: self
+                .topology
+                .as_ref()
+                .and_then(|t| t.cpu(cpu_id))
+                .map_or(-1, |t| t.cluster_id),
+            numa_node: self.numa.as_ref().and_then(|t| t.node_of_cpu(cpu_id)),
+            core_class: cpu_capacity.and_then(|c| CoreClass::from_capacity(c, &self.capacities)),
+            sve_lengths: None,
+            sme_lengths: None,
+            ctr: None,
+            dczid: None,
+        }
     }
-    #[cfg(windows)]
+}
+
+/// Builds ARM CPU information for logical CPU `cpu_id` from procfs/sysfs files only.
+/// Works on any host, so captured trees can be analysed off-target.
+/// Features come from the `/proc/cpuinfo` `Features` line.
+pub fn gather_from(src: &Source, cpu_id: usize) -> ArmCpuInfo {
+    SystemView::read(src).cpu(src, cpu_id)
+}
+
+/// Builds ARM CPU information for every online logical CPU, sorted by CPU ID.
+/// Like [`gather_from`], this only reads procfs/sysfs files.
+pub fn gather_all_from(src: &Source) -> Vec<ArmCpuInfo> {
+    let view = SystemView::read(src);
+    view.cpu_ids.iter().map(|&cpu| view.cpu(src, cpu)).collect()
+}
+
+/// Gathers system-wide ARM/ARM64 CPU information on macOS.
+#[cfg(all(any(target_arch = "arm", target_arch = "aarch64"), target_os = "macos"))]
+fn gather_system() -> ArmCpuInfo {
+    let mut buf = [0u8; 64];
+    let mut len = buf.len();
     unsafe {
-        // Windows ARM feature checks omitted
+        sysctlbyname(
+            b"machdep.cpu.brand_string\0".as_ptr() as *const i8,
+            buf.as_mut_ptr() as *mut c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        );
     }
+    ArmCpuInfo {
+        cpu_id: 0,
+        vendor: "Apple".to_string(),
+        brand: String::from_utf8_lossy(&buf[..len]).trim().to_string(),
+        features: ArmFeatures::empty(),
+        feature_mismatch: None,
+        cores: unsafe { sysconf(_SC_NPROCESSORS_ONLN) as u32 },
+        threads_per_core: 1,
+        caches: Vec::new(),
+        midr: None,
+        id_registers: None,
+        cpu_capacity: None,
+        cluster_id: -1,
+        numa_node: None,
+        core_class: None,
+        sve_lengths: None,
+        sme_lengths: None,
+        ctr: None,
+        dczid: None,
+    }
+}
 
-    // Topology
-    let cores = unsafe {
-        #[cfg(any(target_os = "linux", target_os = "macos"))]
-        {
-            sysconf(_SC_NPROCESSORS_ONLN) as u32
-        }
+/// Gathers system-wide ARM/ARM64 CPU information on platforms other than Linux and macOS.
+#[cfg(all(
+    any(target_arch = "arm", target_arch = "aarch64"),
+    not(any(target_os = "linux", target_os = "macos"))
+))]
+fn gather_system() -> ArmCpuInfo {
+    ArmCpuInfo {
+        cpu_id: 0,
+        vendor: "Unknown".to_string(),
+        brand: "ARM CPU".to_string(),
+        features: ArmFeatures::empty(),
+        feature_mismatch: None,
         #[cfg(windows)]
-        {
-            let mut info = std::mem::zeroed();
-            GetNativeSystemInfo(&mut info);
-            info.dwNumberOfProcessors
-        }
-        #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
-        {
-            1
+        cores: unsafe {
+            let mut sys = std::mem::zeroed();
+            GetNativeSystemInfo(&mut sys);
+            sys.dwNumberOfProcessors
+        },
+        #[cfg(not(windows))]
+        cores: 1,
+        threads_per_core: 1,
+        caches: Vec::new(),
+        midr: None,
+        id_registers: None,
+        cpu_capacity: None,
+        cluster_id: -1,
+        numa_node: None,
+        core_class: None,
+        sve_lengths: None,
+        sme_lengths: None,
+        ctr: None,
+        dczid: None,
+    }
+}
+
+/// Cache of all logical CPU infos, sorted by OS CPU ID. Populated on first use.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+static CPU_INFOS: Lazy<Arc<Vec<ArmCpuInfo>>> = Lazy::new(|| {
+    #[cfg(target_os = "linux")]
+    #[allow(unused_mut)]
+    let mut infos = {
+        let infos = gather_all_from(&Source::live());
+        if infos.is_empty() {
+            vec![gather_from(&Source::live(), 0)]
+        } else {
+            infos
         }
     };
-    let threads_per_core = 1;
+    #[cfg(not(target_os = "linux"))]
+    #[allow(unused_mut)]
+    let mut infos = vec![gather_system()];
 
-    ArmCpuInfo {
-        vendor,
-        brand,
-        features: f,
-        cores,
-        threads_per_core,
+    // Features are system-wide: the kernel only reports those common to every core
+    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+    {
+        apply_hwcaps(&mut infos, unsafe {
+            ArmFeatures::from_hwcaps(
+                getauxval(AT_HWCAP),
+                getauxval(AT_HWCAP2),
+                getauxval(AT_HWCAP3),
+            )
+        });
+        let features = infos[0].features;
+        let id_registers = IdRegisters::read();
+        let sve_lengths = features
+            .contains(ArmFeatures::SVE)
+            .then(|| vector::query(vector::VectorKind::Sve))
+            .flatten();
+        let sme_lengths = features
+            .contains(ArmFeatures::SME)
+            .then(|| vector::query(vector::VectorKind::Sme))
+            .flatten();
+        for info in &mut infos {
+            info.id_registers = id_registers;
+            info.sve_lengths = sve_lengths.clone();
+            info.sme_lengths = sme_lengths.clone();
+        }
     }
+    #[cfg(all(target_os = "linux", target_arch = "arm"))]
+    apply_hwcaps(&mut infos, unsafe {
+        ArmFeatures::from_hwcaps32(getauxval(AT_HWCAP), getauxval(AT_HWCAP2))
+    });
+    // macOS and Windows feature detection omitted for brevity
+
+    // The cache ID registers are readable at EL0 on every aarch64 OS
+    #[cfg(target_arch = "aarch64")]
+    {
+        let (ctr, dczid) = (Ctr::read(), Dczid::read());
+        for info in &mut infos {
+            info.ctr = Some(ctr);
+            info.dczid = Some(dczid);
+        }
+    }
+
+    Arc::new(infos)
+});
+
+/// Replaces the `/proc/cpuinfo` features with the auxiliary vector ones, recording
+/// any disagreement. Keeps the `/proc/cpuinfo` features if getauxval reported nothing.
+#[cfg(all(target_os = "linux", any(target_arch = "arm", target_arch = "aarch64")))]
+fn apply_hwcaps(infos: &mut [ArmCpuInfo], hwcaps: ArmFeatures) {
+    for info in infos {
+        info.feature_mismatch = FeatureMismatch::between(hwcaps, info.features);
+        if !hwcaps.is_empty() {
+            info.features = hwcaps;
+        }
+    }
+}
+
+/// Returns the index of the current logical CPU.
+#[cfg(all(target_os = "linux", any(target_arch = "arm", target_arch = "aarch64")))]
+fn current_cpu_id() -> usize {
+    unsafe { libc::sched_getcpu() }.max(0) as usize
+}
+#[cfg(all(
+    not(target_os = "linux"),
+    any(target_arch = "arm", target_arch = "aarch64")
+))]
+fn current_cpu_id() -> usize {
+    0
+}
+
+/// Returns the `ArmCpuInfo` for the current logical CPU.
+/// Falls back to the first CPU if the current one is not in the cache.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+pub fn gather() -> ArmCpuInfo {
+    info_for_cpu(current_cpu_id()).unwrap_or_else(|| CPU_INFOS[0].clone())
+}
+
+/// Looks up cached info for the logical CPU with the given OS CPU ID.
+/// Returns `None` if that CPU was offline when the cache was built.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+pub fn info_for_cpu(cpu_id: usize) -> Option<ArmCpuInfo> {
+    CPU_INFOS
+        .binary_search_by_key(&cpu_id, |info| info.cpu_id)
+        .ok()
+        .map(|i| CPU_INFOS[i].clone())
+}
+
+/// Returns all cached logical CPU infos.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+pub fn all_cpuinfos() -> Arc<Vec<ArmCpuInfo>> {
+    CPU_INFOS.clone()
 }
 
 impl fmt::Display for ArmCpuInfo {
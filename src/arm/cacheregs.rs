@@ -0,0 +1,99 @@
+// src/arm/cacheregs.rs
+//! Decoding of the EL0-readable cache ID registers CTR_EL0 and DCZID_EL0.
+//!
+//! Size fields hold log2 of a number of 4-byte words; the decoded values are in bytes.
+
+/// Converts a log2-of-words field to bytes.
+fn words_to_bytes(log2: u64) -> u32 {
+    4 << log2
+}
+
+/// Decoded Cache Type Register (CTR_EL0).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ctr {
+    /// Smallest instruction cache line size, in bytes (IminLine)
+    pub i_min_line: u32,
+    /// Smallest data or unified cache line size, in bytes (DminLine)
+    pub d_min_line: u32,
+    /// Data cache clean to the point of unification is not required for
+    /// instruction to data coherence (IDC)
+    pub idc: bool,
+    /// Instruction cache invalidation to the point of unification is not required for
+    /// data to instruction coherence (DIC)
+    pub dic: bool,
+    /// Cache writeback granule in bytes (CWG), `None` if not reported
+    pub cwg: Option<u32>,
+    /// Exclusives reservation granule in bytes (ERG), `None` if not reported
+    pub erg: Option<u32>,
+    /// Level 1 instruction cache policy (L1Ip): 1 = AIVIVT, 2 = VIPT, 3 = PIPT
+    pub l1_ip: u8,
+}
+
+impl Ctr {
+    /// Decodes a raw CTR_EL0 value.
+    pub fn from_raw(raw: u64) -> Ctr {
+        let granule = |shift: u32| match (raw >> shift) & 0xf {
+            0 => None,
+            n => Some(words_to_bytes(n)),
+        };
+        Ctr {
+            i_min_line: words_to_bytes(raw & 0xf),
+            d_min_line: words_to_bytes((raw >> 16) & 0xf),
+            idc: raw & (1 << 28) != 0,
+            dic: raw & (1 << 29) != 0,
+            cwg: granule(24),
+            erg: granule(20),
+            l1_ip: ((raw >> 14) & 0x3) as u8,
+        }
+    }
+
+    /// Reads CTR_EL0 of the running CPU.
+    #[cfg(target_arch = "aarch64")]
+    pub fn read() -> Ctr {
+        let raw: u64;
+        unsafe { core::arch::asm!("mrs {0}, ctr_el0", out(reg) raw, options(nomem, nostack)) };
+        Ctr::from_raw(raw)
+    }
+
+    /// Returns `true` if code written as data must be followed by `IC IVAU` before it runs.
+    pub fn requires_icache_invalidation(&self) -> bool {
+        !self.dic
+    }
+
+    /// Returns `true` if code written as data must be cleaned with `DC CVAU` before it runs.
+    pub fn requires_dcache_clean(&self) -> bool {
+        !self.idc
+    }
+}
+
+/// Decoded Data Cache Zero ID Register (DCZID_EL0).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dczid {
+    /// Size of the block zeroed by `DC ZVA`, in bytes (BS)
+    pub block_size: u32,
+    /// `DC ZVA` is prohibited (DZP)
+    pub prohibited: bool,
+}
+
+impl Dczid {
+    /// Decodes a raw DCZID_EL0 value.
+    pub fn from_raw(raw: u64) -> Dczid {
+        Dczid {
+            block_size: words_to_bytes(raw & 0xf),
+            prohibited: raw & (1 << 4) != 0,
+        }
+    }
+
+    /// Reads DCZID_EL0 of the running CPU.
+    #[cfg(target_arch = "aarch64")]
+    pub fn read() -> Dczid {
+        let raw: u64;
+        unsafe { core::arch::asm!("mrs {0}, dczid_el0", out(reg) raw, options(nomem, nostack)) };
+        Dczid::from_raw(raw)
+    }
+
+    /// Returns the `DC ZVA` block size, or `None` if the instruction may not be used.
+    pub fn zva_block_size(&self) -> Option<u32> {
+        (!self.prohibited).then_some(self.block_size)
+    }
+}
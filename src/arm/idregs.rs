@@ -0,0 +1,334 @@
+// src/arm/idregs.rs
+//! Decoding of the aarch64 `ID_AA64*_EL1` feature ID registers.
+//!
+//! Linux lets EL0 read these registers through MRS emulation when
+//! `HWCAP_CPUID` is set. The values are sanitised to the features the kernel
+//! supports on every CPU in the system.
+
+use super::ArmFeatures;
+
+/// Raw values of the aarch64 feature ID registers.
+/// Registers the CPU or kernel does not expose read as `0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IdRegisters {
+    /// ID_AA64ISAR0_EL1 (instruction set attributes 0)
+    pub isar0: u64,
+    /// ID_AA64ISAR1_EL1 (instruction set attributes 1)
+    pub isar1: u64,
+    /// ID_AA64ISAR2_EL1 (instruction set attributes 2)
+    pub isar2: u64,
+    /// ID_AA64PFR0_EL1 (processor features 0)
+    pub pfr0: u64,
+    /// ID_AA64PFR1_EL1 (processor features 1)
+    pub pfr1: u64,
+    /// ID_AA64ZFR0_EL1 (SVE features)
+    pub zfr0: u64,
+    /// ID_AA64SMFR0_EL1 (SME features)
+    pub smfr0: u64,
+    /// ID_AA64MMFR0_EL1 (memory model features 0)
+    pub mmfr0: u64,
+    /// ID_AA64MMFR1_EL1 (memory model features 1)
+    pub mmfr1: u64,
+    /// ID_AA64MMFR2_EL1 (memory model features 2)
+    pub mmfr2: u64,
+    /// ID_AA64MMFR3_EL1 (memory model features 3)
+    pub mmfr3: u64,
+    /// Read through the kernel's MRS emulation, which also hides fields that are
+    /// not features, such as ID_AA64MMFR0_EL1.PARange. `false` for raw register dumps.
+    pub emulated: bool,
+}
+
+/// Level of the atomic instructions (ID_AA64ISAR0_EL1.Atomic).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AtomicOps {
+    /// Only load/store-exclusive
+    None,
+    /// FEAT_LSE: CAS, SWP, LDADD and friends
+    Lse,
+    /// FEAT_LSE128: 128-bit atomics
+    Lse128,
+}
+
+/// SVE version (ID_AA64ZFR0_EL1.SVEver).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SveVersion {
+    /// FEAT_SVE
+    Sve,
+    /// FEAT_SVE2
+    Sve2,
+    /// FEAT_SVE2p1
+    Sve2p1,
+    /// FEAT_SVE2p2
+    Sve2p2,
+}
+
+/// SME version (ID_AA64SMFR0_EL1.SMEver).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SmeVersion {
+    /// FEAT_SME
+    Sme,
+    /// FEAT_SME2
+    Sme2,
+    /// FEAT_SME2p1
+    Sme2p1,
+    /// FEAT_SME2p2
+    Sme2p2,
+}
+
+/// Pointer authentication algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacAlgorithm {
+    /// Architected QARMA5 (ID_AA64ISAR1_EL1.APA/GPA)
+    Qarma5,
+    /// Architected QARMA3 (ID_AA64ISAR2_EL1.APA3/GPA3)
+    Qarma3,
+    /// IMPLEMENTATION DEFINED algorithm (ID_AA64ISAR1_EL1.API/GPI)
+    ImplementationDefined,
+}
+
+/// Multi-level architecture extension fields decoded from the ID registers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArmExtensions {
+    /// Atomic instruction level
+    pub atomics: AtomicOps,
+    /// SVE version, `None` without SVE
+    pub sve: Option<SveVersion>,
+    /// SME version, `None` without SME
+    pub sme: Option<SmeVersion>,
+    /// Pointer authentication algorithm, `None` without PAuth
+    pub pac: Option<PacAlgorithm>,
+    /// Pointer authentication level (1 = PAuth, 2 = EPAC, 3 = PAuth2, 4 = FPAC,
+    /// 5 = FPACCOMBINE), `0` without PAuth
+    pub pauth_level: u8,
+    /// Load-acquire RCpc level (1 = LRCPC, 2 = LRCPC2, 3 = LRCPC3), `0` if absent
+    pub lrcpc: u8,
+    /// Memory tagging level (1 = EL0 instructions only, 2 = MTE2, 3 = MTE3), `0` if absent
+    pub mte: u8,
+    /// Branch target identification
+    pub bti: bool,
+    /// Physical address size in bits, `None` if the registers came from MRS emulation
+    pub pa_bits: Option<u8>,
+}
+
+/// Extracts the 4-bit field starting at `shift`.
+fn field(reg: u64, shift: u32) -> u8 {
+    ((reg >> shift) & 0xf) as u8
+}
+
+impl IdRegisters {
+    /// Reads the ID registers of the running CPU via MRS emulation.
+    /// Returns `None` if the kernel does not advertise `HWCAP_CPUID`.
+    #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+    pub fn read() -> Option<IdRegisters> {
+        if unsafe { libc::getauxval(libc::AT_HWCAP) } & libc::HWCAP_CPUID == 0 {
+            return None;
+        }
+        // Generic encodings, so the assembler does not need to know newer register names
+        macro_rules! mrs {
+            ($reg:literal) => {{
+                let v: u64;
+                unsafe {
+                    core::arch::asm!(concat!("mrs {0}, ", $reg), out(reg) v, options(nomem, nostack))
+                };
+                v
+            }};
+        }
+        Some(IdRegisters {
+            isar0: mrs!("S3_0_C0_C6_0"),
+            isar1: mrs!("S3_0_C0_C6_1"),
+            isar2: mrs!("S3_0_C0_C6_2"),
+            pfr0: mrs!("S3_0_C0_C4_0"),
+            pfr1: mrs!("S3_0_C0_C4_1"),
+            zfr0: mrs!("S3_0_C0_C4_4"),
+            smfr0: mrs!("S3_0_C0_C4_5"),
+            mmfr0: mrs!("S3_0_C0_C7_0"),
+            mmfr1: mrs!("S3_0_C0_C7_1"),
+            mmfr2: mrs!("S3_0_C0_C7_2"),
+            mmfr3: mrs!("S3_0_C0_C7_3"),
+            emulated: true,
+        })
+    }
+
+    /// Decodes the registers into feature flags, following the kernel's hwcap rules.
+    /// Flags that only the kernel decides, such as `CPUID` and `EVTSTRM`, are never set.
+    pub fn features(&self) -> ArmFeatures {
+        let mut f = ArmFeatures::empty();
+        let mut set = |cond: bool, flag: ArmFeatures| {
+            if cond {
+                f.insert(flag);
+            }
+        };
+
+        let r = self.isar0;
+        set(field(r, 4) >= 1, ArmFeatures::AES);
+        set(field(r, 4) >= 2, ArmFeatures::PMULL);
+        set(field(r, 8) >= 1, ArmFeatures::SHA1);
+        set(field(r, 12) >= 1, ArmFeatures::SHA2);
+        set(field(r, 12) >= 2, ArmFeatures::SHA512);
+        set(field(r, 16) >= 1, ArmFeatures::CRC32);
+        set(field(r, 20) >= 2, ArmFeatures::ATOMICS);
+        set(field(r, 20) >= 3, ArmFeatures::LSE128);
+        set(field(r, 28) >= 1, ArmFeatures::ASIMDRDM);
+        set(field(r, 32) >= 1, ArmFeatures::SHA3);
+        set(field(r, 36) >= 1, ArmFeatures::SM3);
+        set(field(r, 40) >= 1, ArmFeatures::SM4);
+        set(field(r, 44) >= 1, ArmFeatures::ASIMDDP);
+        set(field(r, 48) >= 1, ArmFeatures::ASIMDFHM);
+        set(field(r, 52) >= 1, ArmFeatures::FLAGM);
+        set(field(r, 52) >= 2, ArmFeatures::FLAGM2);
+        set(field(r, 60) >= 1, ArmFeatures::RNG);
+
+        let r = self.isar1;
+        set(field(r, 0) >= 1, ArmFeatures::DCPOP);
+        set(field(r, 0) >= 2, ArmFeatures::DCPODP);
+        set(field(r, 12) >= 1, ArmFeatures::JSCVT);
+        set(field(r, 16) >= 1, ArmFeatures::FCMA);
+        set(field(r, 20) >= 1, ArmFeatures::LRCPC);
+        set(field(r, 20) >= 2, ArmFeatures::ILRCPC);
+        set(field(r, 20) >= 3, ArmFeatures::LRCPC3);
+        set(field(r, 32) >= 1, ArmFeatures::FRINT);
+        set(field(r, 36) >= 1, ArmFeatures::SB);
+        set(field(r, 44) >= 1, ArmFeatures::BF16);
+        set(field(r, 44) >= 2, ArmFeatures::EBF16);
+        set(field(r, 48) >= 1, ArmFeatures::DGH);
+        set(field(r, 52) >= 1, ArmFeatures::I8MM);
+
+        let r = self.isar2;
+        set(field(r, 0) >= 2, ArmFeatures::WFXT);
+        set(field(r, 4) >= 1, ArmFeatures::RPRES);
+        set(field(r, 16) >= 1, ArmFeatures::MOPS);
+        set(field(r, 20) >= 1, ArmFeatures::HBC);
+        set(field(r, 48) >= 1, ArmFeatures::RPRFM);
+        set(field(r, 52) >= 1, ArmFeatures::CSSC);
+        set(field(r, 56) >= 1, ArmFeatures::LUT);
+
+        let pac = self.pac_algorithm().is_some();
+        set(pac, ArmFeatures::PACA);
+        set(
+            pac && (field(self.isar1, 24) | field(self.isar1, 28) | field(self.isar2, 8)) != 0,
+            ArmFeatures::PACG,
+        );
+
+        // FP and AdvSIMD are signed fields: 0xF means not implemented
+        let r = self.pfr0;
+        set(field(r, 16) != 0xf, ArmFeatures::FP);
+        set(field(r, 16) == 1, ArmFeatures::FPHP);
+        set(field(r, 20) != 0xf, ArmFeatures::NEON);
+        set(field(r, 20) == 1, ArmFeatures::ASIMDHP);
+        set(field(r, 48) >= 1, ArmFeatures::DIT);
+
+        let r = self.pfr1;
+        set(field(r, 0) >= 1, ArmFeatures::BTI);
+        set(field(r, 4) >= 2, ArmFeatures::SSBS);
+        set(field(r, 8) >= 2, ArmFeatures::MTE);
+        set(field(r, 8) >= 3, ArmFeatures::MTE3);
+        set(field(r, 44) >= 1, ArmFeatures::GCS);
+
+        set(field(self.mmfr0, 60) >= 1, ArmFeatures::ECV);
+        set(field(self.mmfr1, 44) >= 1, ArmFeatures::AFP);
+        set(field(self.mmfr2, 32) >= 1, ArmFeatures::USCAT);
+        set(field(self.mmfr3, 16) >= 1, ArmFeatures::POE);
+
+        if field(self.pfr0, 32) >= 1 {
+            let r = self.zfr0;
+            set(true, ArmFeatures::SVE);
+            set(field(r, 0) >= 1, ArmFeatures::SVE2);
+            set(field(r, 0) >= 2, ArmFeatures::SVE2P1);
+            set(field(r, 0) >= 3, ArmFeatures::SVE2P2);
+            set(field(r, 4) >= 1, ArmFeatures::SVEAES);
+            set(field(r, 4) >= 2, ArmFeatures::SVEPMULL);
+            set(field(r, 16) >= 1, ArmFeatures::SVEBITPERM);
+            set(field(r, 20) >= 1, ArmFeatures::SVEBF16);
+            set(field(r, 20) >= 2, ArmFeatures::SVE_EBF16);
+            set(field(r, 24) >= 1, ArmFeatures::SVE_B16B16);
+            set(field(r, 32) >= 1, ArmFeatures::SVESHA3);
+            set(field(r, 40) >= 1, ArmFeatures::SVESM4);
+            set(field(r, 44) >= 1, ArmFeatures::SVEI8MM);
+            set(field(r, 52) >= 1, ArmFeatures::SVEF32MM);
+            set(field(r, 56) >= 1, ArmFeatures::SVEF64MM);
+        }
+
+        if field(self.pfr1, 24) >= 1 {
+            let r = self.smfr0;
+            let bit = |n: u32| r & (1 << n) != 0;
+            set(true, ArmFeatures::SME);
+            set(bit(32), ArmFeatures::SME_F32F32);
+            set(bit(33), ArmFeatures::SME_BI32I32);
+            set(bit(34), ArmFeatures::SME_B16F32);
+            set(bit(35), ArmFeatures::SME_F16F32);
+            set(field(r, 36) == 0xf, ArmFeatures::SME_I8I32);
+            set(bit(40), ArmFeatures::SME_F8F32);
+            set(bit(41), ArmFeatures::SME_F8F16);
+            set(bit(42), ArmFeatures::SME_F16F16);
+            set(bit(43), ArmFeatures::SME_B16B16);
+            set(field(r, 44) == 0x5, ArmFeatures::SME_I16I32);
+            set(bit(48), ArmFeatures::SME_F64F64);
+            set(field(r, 52) == 0xf, ArmFeatures::SME_I16I64);
+            set(field(r, 56) >= 1, ArmFeatures::SME2);
+            set(field(r, 56) >= 2, ArmFeatures::SME2P1);
+            set(field(r, 56) >= 3, ArmFeatures::SME2P2);
+            set(bit(60), ArmFeatures::SME_LUTV2);
+            set(bit(63), ArmFeatures::SME_FA64);
+        }
+        f
+    }
+
+    /// Returns the pointer authentication algorithm, preferring the architected ones.
+    fn pac_algorithm(&self) -> Option<PacAlgorithm> {
+        if field(self.isar1, 4) != 0 {
+            Some(PacAlgorithm::Qarma5)
+        } else if field(self.isar2, 12) != 0 {
+            Some(PacAlgorithm::Qarma3)
+        } else if field(self.isar1, 8) != 0 {
+            Some(PacAlgorithm::ImplementationDefined)
+        } else {
+            None
+        }
+    }
+
+    /// Decodes the multi-level extension fields.
+    pub fn extensions(&self) -> ArmExtensions {
+        let pac = self.pac_algorithm();
+        let pauth_level = match pac {
+            Some(PacAlgorithm::Qarma5) => field(self.isar1, 4),
+            Some(PacAlgorithm::Qarma3) => field(self.isar2, 12),
+            Some(PacAlgorithm::ImplementationDefined) => field(self.isar1, 8),
+            None => 0,
+        };
+        ArmExtensions {
+            atomics: match field(self.isar0, 20) {
+                0 | 1 => AtomicOps::None,
+                2 => AtomicOps::Lse,
+                _ => AtomicOps::Lse128,
+            },
+            sve: (field(self.pfr0, 32) >= 1).then(|| match field(self.zfr0, 0) {
+                0 => SveVersion::Sve,
+                1 => SveVersion::Sve2,
+                2 => SveVersion::Sve2p1,
+                _ => SveVersion::Sve2p2,
+            }),
+            sme: (field(self.pfr1, 24) >= 1).then(|| match field(self.smfr0, 56) {
+                0 => SmeVersion::Sme,
+                1 => SmeVersion::Sme2,
+                2 => SmeVersion::Sme2p1,
+                _ => SmeVersion::Sme2p2,
+            }),
+            pac,
+            pauth_level,
+            lrcpc: field(self.isar1, 20),
+            mte: field(self.pfr1, 8),
+            bti: field(self.pfr1, 0) >= 1,
+            // The emulation reads PARange as 0, which would claim 32 bits
+            pa_bits: (!self.emulated).then(|| match field(self.mmfr0, 0) {
+                0 => 32,
+                1 => 36,
+                2 => 40,
+                3 => 42,
+                4 => 44,
+                5 => 48,
+                6 => 52,
+                _ => 56,
+            }),
+        }
+    }
+}
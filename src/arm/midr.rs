@@ -0,0 +1,271 @@
+// src/arm/midr.rs
+//! Main ID Register (MIDR_EL1) decoding and the implementer/part database.
+
+use crate::sysfs::{ProcCpuEntry, Source};
+use core::fmt;
+
+/// Decoded Main ID Register of an ARM core.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Midr {
+    /// Implementer code (e.g. 0x41 for Arm Ltd.)
+    pub implementer: u8,
+    /// Major revision (the `r` in `rNpM`)
+    pub variant: u8,
+    /// Architecture code (0xF means "defined by ID registers")
+    pub architecture: u8,
+    /// Implementer-defined part number
+    pub part: u16,
+    /// Minor revision (the `p` in `rNpM`)
+    pub revision: u8,
+}
+
+impl Midr {
+    /// Decodes a raw MIDR_EL1 value.
+    pub fn from_raw(raw: u64) -> Midr {
+        Midr {
+            implementer: ((raw >> 24) & 0xff) as u8,
+            variant: ((raw >> 20) & 0xf) as u8,
+            architecture: ((raw >> 16) & 0xf) as u8,
+            part: ((raw >> 4) & 0xfff) as u16,
+            revision: (raw & 0xf) as u8,
+        }
+    }
+
+    /// Re-encodes the register value.
+    pub fn raw(&self) -> u64 {
+        (self.implementer as u64) << 24
+            | (self.variant as u64 & 0xf) << 20
+            | (self.architecture as u64 & 0xf) << 16
+            | (self.part as u64 & 0xfff) << 4
+            | (self.revision as u64 & 0xf)
+    }
+
+    /// Builds a MIDR from the `CPU implementer`, `CPU variant`, `CPU architecture`,
+    /// `CPU part` and `CPU revision` fields of a `/proc/cpuinfo` block.
+    pub fn from_proc_entry(entry: &ProcCpuEntry) -> Option<Midr> {
+        let num = |key: &str| -> Option<u64> {
+            let v = entry.get(key)?;
+            match v.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => v.parse().ok(),
+            }
+        };
+        Some(Midr {
+            implementer: num("CPU implementer")? as u8,
+            variant: num("CPU variant").unwrap_or(0) as u8,
+            // 32-bit kernels print e.g. "7", 64-bit kernels "8"; the register holds 0xF
+            architecture: 0xf,
+            part: num("CPU part")? as u16,
+            revision: num("CPU revision").unwrap_or(0) as u8,
+        })
+    }
+
+    /// Name of the implementer, e.g. `"ARM"` or `"Apple"`.
+    pub fn vendor_name(&self) -> Option<&'static str> {
+        implementer_name(self.implementer)
+    }
+
+    /// Name of the core, e.g. `"Cortex-A76"` or `"Neoverse V2"`.
+    pub fn part_name(&self) -> Option<&'static str> {
+        part_name(self.implementer, self.part)
+    }
+}
+
+impl fmt::Display for Midr {
+    /// Formats as `"<vendor> <part> r<variant>p<revision>"`, using hex codes for unknown IDs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.vendor_name() {
+            Some(v) => write!(f, "{}", v)?,
+            None => write!(f, "0x{:02x}", self.implementer)?,
+        }
+        match self.part_name() {
+            Some(p) => write!(f, " {}", p)?,
+            None => write!(f, " 0x{:03x}", self.part)?,
+        }
+        write!(f, " r{}p{}", self.variant, self.revision)
+    }
+}
+
+/// Maps an implementer code to a vendor name.
+pub fn implementer_name(implementer: u8) -> Option<&'static str> {
+    Some(match implementer {
+        0x41 => "ARM",
+        0x42 => "Broadcom",
+        0x43 => "Cavium",
+        0x44 => "DEC",
+        0x46 => "Fujitsu",
+        0x48 => "HiSilicon",
+        0x49 => "Infineon",
+        0x4d => "Freescale",
+        0x4e => "NVIDIA",
+        0x50 => "Applied Micro",
+        0x51 => "Qualcomm",
+        0x53 => "Samsung",
+        0x56 => "Marvell",
+        0x61 => "Apple",
+        0x66 => "Faraday",
+        0x69 => "Intel",
+        0x6d => "Microsoft",
+        0x70 => "Phytium",
+        0xc0 => "Ampere",
+        _ => return None,
+    })
+}
+
+/// Maps an implementer code and part number to a core name.
+pub fn part_name(implementer: u8, part: u16) -> Option<&'static str> {
+    Some(match (implementer, part) {
+        // Arm Ltd.
+        (0x41, 0xb76) => "ARM1176",
+        (0x41, 0xc05) => "Cortex-A5",
+        (0x41, 0xc07) => "Cortex-A7",
+        (0x41, 0xc08) => "Cortex-A8",
+        (0x41, 0xc09) => "Cortex-A9",
+        (0x41, 0xc0d) => "Cortex-A12",
+        (0x41, 0xc0e) => "Cortex-A17",
+        (0x41, 0xc0f) => "Cortex-A15",
+        (0x41, 0xd02) => "Cortex-A34",
+        (0x41, 0xd03) => "Cortex-A53",
+        (0x41, 0xd04) => "Cortex-A35",
+        (0x41, 0xd05) => "Cortex-A55",
+        (0x41, 0xd06) => "Cortex-A65",
+        (0x41, 0xd07) => "Cortex-A57",
+        (0x41, 0xd08) => "Cortex-A72",
+        (0x41, 0xd09) => "Cortex-A73",
+        (0x41, 0xd0a) => "Cortex-A75",
+        (0x41, 0xd0b) => "Cortex-A76",
+        (0x41, 0xd0c) => "Neoverse N1",
+        (0x41, 0xd0d) => "Cortex-A77",
+        (0x41, 0xd0e) => "Cortex-A76AE",
+        (0x41, 0xd13) => "Cortex-R52",
+        (0x41, 0xd15) => "Cortex-R82",
+        (0x41, 0xd40) => "Neoverse V1",
+        (0x41, 0xd41) => "Cortex-A78",
+        (0x41, 0xd42) => "Cortex-A78AE",
+        (0x41, 0xd43) => "Cortex-A65AE",
+        (0x41, 0xd44) => "Cortex-X1",
+        (0x41, 0xd46) => "Cortex-A510",
+        (0x41, 0xd47) => "Cortex-A710",
+        (0x41, 0xd48) => "Cortex-X2",
+        (0x41, 0xd49) => "Neoverse N2",
+        (0x41, 0xd4a) => "Neoverse E1",
+        (0x41, 0xd4b) => "Cortex-A78C",
+        (0x41, 0xd4c) => "Cortex-X1C",
+        (0x41, 0xd4d) => "Cortex-A715",
+        (0x41, 0xd4e) => "Cortex-X3",
+        (0x41, 0xd4f) => "Neoverse V2",
+        (0x41, 0xd80) => "Cortex-A520",
+        (0x41, 0xd81) => "Cortex-A720",
+        (0x41, 0xd82) => "Cortex-X4",
+        (0x41, 0xd83) => "Neoverse V3AE",
+        (0x41, 0xd84) => "Neoverse V3",
+        (0x41, 0xd85) => "Cortex-X925",
+        (0x41, 0xd87) => "Cortex-A725",
+        (0x41, 0xd8e) => "Neoverse N3",
+        // Broadcom
+        (0x42, 0x00f) => "Brahma-B15",
+        (0x42, 0x100) => "Brahma-B53",
+        (0x42, 0x516) => "Vulcan",
+        // Cavium
+        (0x43, 0x0a1) => "ThunderX",
+        (0x43, 0x0a2) => "ThunderX 81XX",
+        (0x43, 0x0a3) => "ThunderX 83XX",
+        (0x43, 0x0af) => "ThunderX2",
+        // Fujitsu
+        (0x46, 0x001) => "A64FX",
+        // HiSilicon
+        (0x48, 0xd01) => "TaiShan v110",
+        (0x48, 0xd02) => "TaiShan v120",
+        // NVIDIA
+        (0x4e, 0x000) => "Denver",
+        (0x4e, 0x003) => "Denver 2",
+        (0x4e, 0x004) => "Carmel",
+        // Qualcomm
+        (0x51, 0x001) => "Oryon",
+        (0x51, 0x00f) | (0x51, 0x02d) => "Scorpion",
+        (0x51, 0x04d) | (0x51, 0x06f) => "Krait",
+        (0x51, 0x201) | (0x51, 0x205) | (0x51, 0x211) => "Kryo",
+        (0x51, 0x800) => "Kryo 2XX Gold",
+        (0x51, 0x801) => "Kryo 2XX Silver",
+        (0x51, 0x802) => "Kryo 3XX Gold",
+        (0x51, 0x803) => "Kryo 3XX Silver",
+        (0x51, 0x804) => "Kryo 4XX Gold",
+        (0x51, 0x805) => "Kryo 4XX Silver",
+        (0x51, 0xc00) => "Falkor",
+        (0x51, 0xc01) => "Saphira",
+        // Samsung
+        (0x53, 0x001) => "Exynos M1",
+        (0x53, 0x002) => "Exynos M3",
+        (0x53, 0x003) => "Exynos M4",
+        (0x53, 0x004) => "Exynos M5",
+        // Marvell
+        (0x56, 0x131) => "Feroceon 88FR131",
+        (0x56, 0x581) => "PJ4/PJ4b",
+        (0x56, 0x584) => "PJ4B-MP",
+        // Apple
+        (0x61, 0x022) => "M1 Icestorm",
+        (0x61, 0x023) => "M1 Firestorm",
+        (0x61, 0x024) => "M1 Pro Icestorm",
+        (0x61, 0x025) => "M1 Pro Firestorm",
+        (0x61, 0x028) => "M1 Max Icestorm",
+        (0x61, 0x029) => "M1 Max Firestorm",
+        (0x61, 0x032) => "M2 Blizzard",
+        (0x61, 0x033) => "M2 Avalanche",
+        (0x61, 0x034) => "M2 Pro Blizzard",
+        (0x61, 0x035) => "M2 Pro Avalanche",
+        (0x61, 0x038) => "M2 Max Blizzard",
+        (0x61, 0x039) => "M2 Max Avalanche",
+        // Microsoft
+        (0x6d, 0xd49) => "Azure Cobalt 100",
+        // Phytium
+        (0x70, 0x660) => "FTC660",
+        (0x70, 0x661) => "FTC661",
+        (0x70, 0x662) => "FTC662",
+        (0x70, 0x663) => "FTC663",
+        // Ampere
+        (0xc0, 0xac3) => "AmpereOne",
+        (0xc0, 0xac4) => "AmpereOne AC04",
+        _ => return None,
+    })
+}
+
+/// Reads the MIDR of one logical CPU on the running system.
+pub fn read_cpu(cpu_id: usize) -> Option<Midr> {
+    read_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Reads the MIDR of one logical CPU from
+/// `cpuN/regs/identification/midr_el1`, falling back to `/proc/cpuinfo`.
+pub fn read_cpu_from(src: &Source, cpu_id: usize) -> Option<Midr> {
+    read_sysfs(src, cpu_id).or_else(|| from_entries(&src.proc_cpuinfo(), cpu_id))
+}
+
+/// Like [`read_cpu_from`], but falls back to the already-parsed `/proc/cpuinfo`
+/// `entries`, so reading many CPUs parses it once.
+pub fn read_cpu_with_entries(
+    src: &Source,
+    cpu_id: usize,
+    entries: &[ProcCpuEntry],
+) -> Option<Midr> {
+    read_sysfs(src, cpu_id).or_else(|| from_entries(entries, cpu_id))
+}
+
+/// Reads `cpuN/regs/identification/midr_el1`.
+fn read_sysfs(src: &Source, cpu_id: usize) -> Option<Midr> {
+    let path = format!(
+        "{}/cpu{}/regs/identification/midr_el1",
+        crate::sysfs::CPU_ROOT,
+        cpu_id
+    );
+    src.read_trimmed(&path)
+        .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .map(Midr::from_raw)
+}
+
+/// Decodes the MIDR fields of CPU `cpu_id`'s `/proc/cpuinfo` block.
+fn from_entries(entries: &[ProcCpuEntry], cpu_id: usize) -> Option<Midr> {
+    entries
+        .iter()
+        .find(|e| e.processor == Some(cpu_id))
+        .and_then(Midr::from_proc_entry)
+}
@@ -0,0 +1,110 @@
+// src/arm/vector.rs
+//! SVE and SME vector length queries.
+//!
+//! Lengths are in bytes, as used by the kernel ABI (16 = 128 bits).
+
+use crate::sysfs::Source;
+
+/// System default SVE vector length for new processes.
+pub const SVE_DEFAULT_VL: &str = "/proc/sys/abi/sve_default_vector_length";
+/// System default SME streaming vector length for new processes.
+pub const SME_DEFAULT_VL: &str = "/proc/sys/abi/sme_default_vector_length";
+
+/// Mask of the vector length in a `PR_SVE_GET_VL`/`PR_SME_GET_VL` result;
+/// the upper bits hold inheritance flags.
+const PR_VL_LEN_MASK: i32 = 0xffff;
+// prctl options from `<linux/prctl.h>`; `libc` only has the SME ones, and only for glibc
+/// Sets the SVE vector length of the calling thread.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const PR_SVE_SET_VL: libc::c_int = 50;
+/// Gets the SVE vector length of the calling thread.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const PR_SVE_GET_VL: libc::c_int = 51;
+/// Sets the SME streaming vector length of the calling thread.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const PR_SME_SET_VL: libc::c_int = 63;
+/// Gets the SME streaming vector length of the calling thread.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const PR_SME_GET_VL: libc::c_int = 64;
+/// Largest vector length the architecture allows, in bytes.
+const VL_ARCH_MAX: u32 = 256;
+
+/// Which vector extension a length refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorKind {
+    /// Scalable Vector Extension
+    Sve,
+    /// Scalable Matrix Extension streaming mode
+    Sme,
+}
+
+/// Vector lengths of one extension, in bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VectorLengths {
+    /// Vector length of the thread that made the query
+    pub current: u32,
+    /// Largest vector length the system supports
+    pub max: u32,
+    /// Every vector length the system supports, ascending
+    pub supported: Vec<u32>,
+    /// Default for new processes, if the kernel exposes it
+    pub default: Option<u32>,
+}
+
+/// Extracts the length from a `PR_*_GET_VL`/`PR_*_SET_VL` result.
+/// Returns `None` for the error value.
+pub fn decode_prctl_vl(ret: i32) -> Option<u32> {
+    (ret >= 0).then_some((ret & PR_VL_LEN_MASK) as u32)
+}
+
+/// Reads the system default vector length on the running system.
+pub fn read_default(kind: VectorKind) -> Option<u32> {
+    read_default_from(&Source::live(), kind)
+}
+
+/// Reads the system default vector length from `/proc/sys/abi`.
+pub fn read_default_from(src: &Source, kind: VectorKind) -> Option<u32> {
+    let path = match kind {
+        VectorKind::Sve => SVE_DEFAULT_VL,
+        VectorKind::Sme => SME_DEFAULT_VL,
+    };
+    src.read_trimmed(path)?.parse().ok()
+}
+
+/// Returns the `(get, set)` prctl options for an extension.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+fn prctl_ops(kind: VectorKind) -> (libc::c_int, libc::c_int) {
+    match kind {
+        VectorKind::Sve => (PR_SVE_GET_VL, PR_SVE_SET_VL),
+        VectorKind::Sme => (PR_SME_GET_VL, PR_SME_SET_VL),
+    }
+}
+
+/// Queries the vector lengths of the running system.
+/// Returns `None` if the kernel or CPU does not support the extension.
+///
+/// The supported set is found by requesting every candidate length on a
+/// scratch thread, so the calling thread's vector length is not changed.
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+pub fn query(kind: VectorKind) -> Option<VectorLengths> {
+    let (get, set) = prctl_ops(kind);
+    let current = decode_prctl_vl(unsafe { libc::prctl(get, 0, 0, 0, 0) })?;
+    let mut supported = std::thread::spawn(move || {
+        (1..=VL_ARCH_MAX / 16)
+            .filter_map(|n| {
+                // The kernel picks the largest supported length not above the request
+                decode_prctl_vl(unsafe { libc::prctl(set, (n * 16) as libc::c_ulong, 0, 0, 0) })
+            })
+            .collect::<Vec<u32>>()
+    })
+    .join()
+    .unwrap_or_default();
+    supported.sort_unstable();
+    supported.dedup();
+    Some(VectorLengths {
+        current,
+        max: supported.last().copied().unwrap_or(current),
+        supported,
+        default: read_default(kind),
+    })
+}
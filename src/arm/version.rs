@@ -0,0 +1,187 @@
+// src/arm/version.rs
+//! Armv8.x / Armv9.x architecture version inference from aarch64 feature flags.
+//!
+//! Only mandatory extensions that the kernel reports as flags are checked, so a
+//! level is an upper bound on what code built for it may assume: features without
+//! a hwcap (PAN, RAS, VHE, ...) are taken on trust.
+
+use super::ArmFeatures;
+use core::fmt;
+
+/// An A-profile architecture version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArmVersion {
+    /// Armv8.0-A
+    V8_0,
+    /// Armv8.1-A
+    V8_1,
+    /// Armv8.2-A
+    V8_2,
+    /// Armv8.3-A
+    V8_3,
+    /// Armv8.4-A
+    V8_4,
+    /// Armv8.5-A
+    V8_5,
+    /// Armv8.6-A
+    V8_6,
+    /// Armv8.7-A
+    V8_7,
+    /// Armv8.8-A
+    V8_8,
+    /// Armv8.9-A
+    V8_9,
+    /// Armv9.0-A
+    V9_0,
+    /// Armv9.1-A
+    V9_1,
+    /// Armv9.2-A
+    V9_2,
+    /// Armv9.3-A
+    V9_3,
+    /// Armv9.4-A
+    V9_4,
+    /// Armv9.5-A
+    V9_5,
+}
+
+/// Every version, in order.
+const VERSIONS: [ArmVersion; 16] = [
+    ArmVersion::V8_0,
+    ArmVersion::V8_1,
+    ArmVersion::V8_2,
+    ArmVersion::V8_3,
+    ArmVersion::V8_4,
+    ArmVersion::V8_5,
+    ArmVersion::V8_6,
+    ArmVersion::V8_7,
+    ArmVersion::V8_8,
+    ArmVersion::V8_9,
+    ArmVersion::V9_0,
+    ArmVersion::V9_1,
+    ArmVersion::V9_2,
+    ArmVersion::V9_3,
+    ArmVersion::V9_4,
+    ArmVersion::V9_5,
+];
+
+impl ArmVersion {
+    /// Returns `(major, minor)`, e.g. `(8, 2)`.
+    pub fn number(self) -> (u8, u8) {
+        let i = self as u8;
+        if i < ArmVersion::V9_0 as u8 {
+            (8, i)
+        } else {
+            (9, i - ArmVersion::V9_0 as u8)
+        }
+    }
+
+    /// Returns the compiler `-march` name, e.g. `"armv8.2-a"`.
+    pub fn march(self) -> String {
+        let (major, minor) = self.number();
+        format!("armv{}.{}-a", major, minor)
+    }
+
+    /// Mandatory extensions this version adds over its predecessor in the same major line
+    /// (Armv8.0 and Armv9.0 list their own base).
+    fn added(self) -> ArmFeatures {
+        use ArmFeatures as F;
+        match self {
+            ArmVersion::V8_0 => F::FP | F::NEON,
+            ArmVersion::V8_1 => F::CRC32 | F::ATOMICS | F::ASIMDRDM,
+            ArmVersion::V8_2 => F::DCPOP,
+            ArmVersion::V8_3 => F::LRCPC | F::PACA | F::PACG | F::JSCVT | F::FCMA,
+            ArmVersion::V8_4 => F::ASIMDDP | F::DIT | F::FLAGM | F::ILRCPC | F::USCAT,
+            ArmVersion::V8_5 => F::FLAGM2 | F::FRINT | F::SSBS | F::SB | F::DCPODP | F::BTI,
+            ArmVersion::V8_6 => F::BF16 | F::ECV | F::I8MM,
+            ArmVersion::V8_7 => F::WFXT,
+            ArmVersion::V8_8 => F::HBC | F::MOPS,
+            ArmVersion::V8_9 => F::CSSC,
+            ArmVersion::V9_0 => F::SVE | F::SVE2,
+            ArmVersion::V9_5 => F::FAMINMAX | F::LUT,
+            // Armv9.1 to 9.4 only pull in Armv8.6 to 8.9
+            _ => F::empty(),
+        }
+    }
+
+    /// Returns every mandatory extension of this version that has a feature flag.
+    /// Armv9.x includes Armv8.(x+5).
+    pub fn required(self) -> ArmFeatures {
+        let (major, minor) = self.number();
+        let v8_minor = if major == 8 {
+            minor
+        } else {
+            (minor + 5).min(9)
+        };
+        let mut f = VERSIONS[..=v8_minor as usize]
+            .iter()
+            .fold(ArmFeatures::empty(), |acc, v| acc | v.added());
+        if major == 9 {
+            f |= VERSIONS[ArmVersion::V9_0 as usize..=self as usize]
+                .iter()
+                .fold(ArmFeatures::empty(), |acc, v| acc | v.added());
+        }
+        f
+    }
+
+    /// Returns the required extensions missing from `features`.
+    pub fn missing(self, features: ArmFeatures) -> ArmFeatures {
+        self.required() - features
+    }
+
+    /// Returns `true` if `features` contains every required extension.
+    pub fn is_satisfied_by(self, features: ArmFeatures) -> bool {
+        self.missing(features).is_empty()
+    }
+}
+
+impl fmt::Display for ArmVersion {
+    /// Formats as e.g. `"Armv8.2-A"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor) = self.number();
+        write!(f, "Armv{}.{}-A", major, minor)
+    }
+}
+
+/// Result of [`infer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionReport {
+    /// Highest version fully satisfied (Armv9 preferred), `None` if not even Armv8.0
+    pub version: Option<ArmVersion>,
+    /// Highest Armv8.x version fully satisfied
+    pub v8: Option<ArmVersion>,
+    /// Highest Armv9.x version fully satisfied
+    pub v9: Option<ArmVersion>,
+    /// The next Armv8.x and Armv9.x versions, each with the extensions blocking it
+    pub next: Vec<(ArmVersion, ArmFeatures)>,
+}
+
+/// Infers the architecture version from an aarch64 feature set.
+pub fn infer(features: ArmFeatures) -> VersionReport {
+    // Levels are cumulative within a line, so stop at the first one that fails
+    let highest = |line: &[ArmVersion]| {
+        line.iter()
+            .take_while(|v| v.is_satisfied_by(features))
+            .last()
+            .copied()
+    };
+    let (v8_line, v9_line) = VERSIONS.split_at(ArmVersion::V9_0 as usize);
+    let v8 = highest(v8_line);
+    let v9 = highest(v9_line);
+
+    let next_in = |line: &[ArmVersion], cur: Option<ArmVersion>| {
+        let i = cur.map_or(0, |v| line.iter().position(|&l| l == v).unwrap() + 1);
+        line.get(i).map(|&v| (v, v.missing(features)))
+    };
+    let next = next_in(v8_line, v8)
+        .into_iter()
+        .chain(next_in(v9_line, v9))
+        .collect();
+
+    VersionReport {
+        version: v9.or(v8),
+        v8,
+        v9,
+        next,
+    }
+}
@@ -0,0 +1,99 @@
+// src/cache.rs
+//! Architecture-independent cache descriptions.
+//!
+//! x86 fills these from CPUID; every architecture can also read them from
+//! `/sys/devices/system/cpu/cpuN/cache/index*/` on Linux.
+
+use crate::sysfs::{self, Source};
+
+/// Kind of data a cache holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheType {
+    /// Data-only cache
+    Data,
+    /// Instruction-only cache
+    Instruction,
+    /// Combined instruction and data cache
+    Unified,
+}
+
+/// Geometry and sharing of a single cache.
+/// Numeric fields are `0` when the source does not report them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// Cache level (1 = L1, ...)
+    pub level: u8,
+    /// Data, instruction or unified
+    pub cache_type: CacheType,
+    /// Total size in bytes
+    pub size: u64,
+    /// Ways of associativity
+    pub ways: u32,
+    /// Coherency line size in bytes
+    pub line_size: u32,
+    /// Number of sets
+    pub sets: u32,
+    /// OS CPU IDs sharing this cache (empty if unknown)
+    pub shared_cpus: Vec<usize>,
+}
+
+/// Parses a sysfs cache size such as `"48K"` or `"2M"` into bytes.
+pub fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (num, mult) = match s.char_indices().last()? {
+        (i, 'K') => (&s[..i], 1 << 10),
+        (i, 'M') => (&s[..i], 1 << 20),
+        (i, 'G') => (&s[..i], 1 << 30),
+        _ => (s, 1),
+    };
+    num.parse::<u64>().ok().map(|n| n * mult)
+}
+
+/// Reads a numeric attribute, returning `0` if it is missing.
+fn read_u32(src: &Source, path: &str) -> u32 {
+    src.read_trimmed(path)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Reads one `cache/indexN` directory.
+fn read_index(src: &Source, dir: &str) -> Option<CacheInfo> {
+    let level = src.read_trimmed(&format!("{}/level", dir))?.parse().ok()?;
+    let cache_type = match src.read_trimmed(&format!("{}/type", dir))?.as_str() {
+        "Data" => CacheType::Data,
+        "Instruction" => CacheType::Instruction,
+        "Unified" => CacheType::Unified,
+        _ => return None,
+    };
+    Some(CacheInfo {
+        level,
+        cache_type,
+        size: src
+            .read_trimmed(&format!("{}/size", dir))
+            .and_then(|s| parse_size(&s))
+            .unwrap_or(0),
+        ways: read_u32(src, &format!("{}/ways_of_associativity", dir)),
+        line_size: read_u32(src, &format!("{}/coherency_line_size", dir)),
+        sets: read_u32(src, &format!("{}/number_of_sets", dir)),
+        shared_cpus: src
+            .read_trimmed(&format!("{}/shared_cpu_list", dir))
+            .map(|s| sysfs::parse_cpu_list(&s))
+            .unwrap_or_default(),
+    })
+}
+
+/// Reads the cache hierarchy of a logical CPU on the running system.
+pub fn read_cpu(cpu_id: usize) -> Vec<CacheInfo> {
+    read_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Reads the cache hierarchy of a logical CPU from sysfs, ordered by level.
+/// Returns an empty list if the kernel does not expose cache information.
+pub fn read_cpu_from(src: &Source, cpu_id: usize) -> Vec<CacheInfo> {
+    let mut caches: Vec<CacheInfo> = (0..)
+        .map(|i| format!("{}/cpu{}/cache/index{}", sysfs::CPU_ROOT, cpu_id, i))
+        .map_while(|dir| read_index(src, &dir))
+        .collect();
+    caches.sort_by_key(|c| c.level);
+    caches
+}
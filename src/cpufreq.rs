@@ -0,0 +1,79 @@
+// src/cpufreq.rs
+//! CPU frequency scaling information from Linux cpufreq sysfs.
+//!
+//! All frequencies are in kHz, as reported by the kernel.
+
+use crate::sysfs::{self, Source};
+
+/// Global cpufreq boost switch (acpi-cpufreq, amd-pstate).
+const BOOST: &str = "/sys/devices/system/cpu/cpufreq/boost";
+/// intel_pstate's inverted boost switch.
+const INTEL_NO_TURBO: &str = "/sys/devices/system/cpu/intel_pstate/no_turbo";
+
+/// Frequency limits and scaling policy of a logical CPU.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpuFreq {
+    /// Base (guaranteed, non-turbo) frequency, if the driver reports it
+    pub base_khz: Option<u32>,
+    /// Lowest frequency the hardware supports
+    pub min_khz: Option<u32>,
+    /// Highest frequency the hardware supports, including boost
+    pub max_khz: Option<u32>,
+    /// Frequency the kernel last observed or requested
+    pub current_khz: Option<u32>,
+    /// Lower limit currently applied by the governor
+    pub scaling_min_khz: Option<u32>,
+    /// Upper limit currently applied by the governor
+    pub scaling_max_khz: Option<u32>,
+    /// Scaling driver (e.g. "intel_pstate", "amd-pstate-epp", "cppc_cpufreq")
+    pub driver: Option<String>,
+    /// Scaling governor (e.g. "schedutil", "performance")
+    pub governor: Option<String>,
+}
+
+/// Reads a kHz attribute.
+fn read_khz(src: &Source, dir: &str, name: &str) -> Option<u32> {
+    src.read_trimmed(&format!("{}/{}", dir, name))?.parse().ok()
+}
+
+/// Reads the cpufreq policy of a logical CPU on the running system.
+pub fn read_cpu(cpu_id: usize) -> Option<CpuFreq> {
+    read_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Reads the cpufreq policy of a logical CPU.
+/// Returns `None` if the CPU has no cpufreq directory (no driver loaded, VM, not Linux).
+pub fn read_cpu_from(src: &Source, cpu_id: usize) -> Option<CpuFreq> {
+    let dir = format!("{}/cpu{}/cpufreq", sysfs::CPU_ROOT, cpu_id);
+    let driver = src.read_trimmed(&format!("{}/scaling_driver", dir));
+    let freq = CpuFreq {
+        base_khz: read_khz(src, &dir, "base_frequency")
+            .or_else(|| read_khz(src, &dir, "cpuinfo_base_freq")),
+        min_khz: read_khz(src, &dir, "cpuinfo_min_freq"),
+        max_khz: read_khz(src, &dir, "cpuinfo_max_freq"),
+        current_khz: read_khz(src, &dir, "scaling_cur_freq")
+            .or_else(|| read_khz(src, &dir, "cpuinfo_cur_freq")),
+        scaling_min_khz: read_khz(src, &dir, "scaling_min_freq"),
+        scaling_max_khz: read_khz(src, &dir, "scaling_max_freq"),
+        driver,
+        governor: src.read_trimmed(&format!("{}/scaling_governor", dir)),
+    };
+    if freq == CpuFreq::default() {
+        return None;
+    }
+    Some(freq)
+}
+
+/// Returns whether frequency boost (turbo) is enabled on the running system.
+pub fn boost_enabled() -> Option<bool> {
+    boost_enabled_from(&Source::live())
+}
+
+/// Returns whether frequency boost (turbo) is enabled system-wide, or `None`
+/// if no driver exposes a boost switch.
+pub fn boost_enabled_from(src: &Source) -> Option<bool> {
+    if let Some(v) = src.read_trimmed(BOOST) {
+        return Some(v == "1");
+    }
+    src.read_trimmed(INTEL_NO_TURBO).map(|v| v == "0")
+}
@@ -5,12 +5,28 @@
 //! This crate provides a unified API for querying CPU features across
 //! x86/x86_64, ARM, and RISC-V architectures.
 
-#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
 /// ARM architecture support module.
+///
+/// Always compiled so captured ARM systems can be analysed on any host;
+/// live detection via `arm::gather` is only available on ARM targets.
 pub mod arm;
-#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+/// Architecture-independent cache descriptions.
+pub mod cache;
+/// CPU frequency scaling information from Linux cpufreq.
+pub mod cpufreq;
+/// NUMA node layout from Linux sysfs.
+pub mod numa;
 /// RISC-V architecture support module.
+///
+/// Always compiled so captured RISC-V systems can be analysed on any host;
+/// live detection via `riscv::gather` is only available on RISC-V targets.
 pub mod riscv;
+/// Linux sysfs/procfs helpers shared by all architectures.
+pub mod sysfs;
+/// Package/die/cluster/core/thread topology from Linux sysfs.
+pub mod topology;
+/// Kernel-reported CPU vulnerability and mitigation status.
+pub mod vulnerabilities;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 /// x86/x86_64 architecture support module.
 pub mod x86;
@@ -102,6 +118,126 @@ pub enum InstructionSet {
     SHA1,
     SHA2,
     CRC32,
+    FP,
+    EVTSTRM,
+    ATOMICS,
+    FPHP,
+    ASIMDHP,
+    CPUID,
+    ASIMDRDM,
+    JSCVT,
+    FCMA,
+    LRCPC,
+    DCPOP,
+    SHA3,
+    SM3,
+    SM4,
+    ASIMDDP,
+    SHA512,
+    SVE,
+    ASIMDFHM,
+    DIT,
+    USCAT,
+    ILRCPC,
+    FLAGM,
+    SSBS,
+    SB,
+    PACA,
+    PACG,
+    GCS,
+    CMPBR,
+    FPRCVT,
+    F8MM8,
+    F8MM4,
+    SveF16mm,
+    SveEltperm,
+    SveAes2,
+    SveBfscale,
+    SVE2P2,
+    SME2P2,
+    SmeSbitperm,
+    SmeAes,
+    SmeSfexpa,
+    SmeStmop,
+    SmeSmop4,
+    DCPODP,
+    SVE2,
+    SVEAES,
+    SVEPMULL,
+    SVEBITPERM,
+    SVESHA3,
+    SVESM4,
+    FLAGM2,
+    FRINT,
+    SVEI8MM,
+    SVEF32MM,
+    SVEF64MM,
+    SVEBF16,
+    I8MM,
+    BF16,
+    DGH,
+    RNG,
+    BTI,
+    MTE,
+    ECV,
+    AFP,
+    RPRES,
+    MTE3,
+    SME,
+    SmeI16i64,
+    SmeF64f64,
+    SmeI8i32,
+    SmeF16f32,
+    SmeB16f32,
+    SmeF32f32,
+    SmeFa64,
+    WFXT,
+    EBF16,
+    SveEbf16,
+    CSSC,
+    RPRFM,
+    SVE2P1,
+    SME2,
+    SME2P1,
+    SmeI16i32,
+    SmeBi32i32,
+    SmeB16b16,
+    SmeF16f16,
+    MOPS,
+    HBC,
+    SveB16b16,
+    LRCPC3,
+    LSE128,
+    FPMR,
+    LUT,
+    FAMINMAX,
+    F8CVT,
+    F8FMA,
+    F8DP4,
+    F8DP2,
+    F8E4M3,
+    F8E5M2,
+    SmeLutv2,
+    SmeF8f16,
+    SmeF8f32,
+    SmeSf8fma,
+    SmeSf8dp4,
+    SmeSf8dp2,
+    POE,
+    MteFar,
+    MteStoreOnly,
+    SWP,
+    HALF,
+    THUMB,
+    FASTMULT,
+    VFP,
+    VFPV3,
+    VFPV3D16,
+    VFPV4,
+    IDIVA,
+    IDIVT,
+    VFPD32,
+    LPAE,
     // RISC-V
     RvI,
     RvM,
@@ -109,6 +245,96 @@ pub enum InstructionSet {
     RvF,
     RvD,
     RvC,
+    RvV,
+    RvH,
+    RvB,
+    RvQ,
+    RvZicsr,
+    RvZifencei,
+    RvZicntr,
+    RvZihpm,
+    RvZicbom,
+    RvZicbop,
+    RvZicboz,
+    RvZicond,
+    RvZihintpause,
+    RvZihintntl,
+    RvZimop,
+    RvZicfilp,
+    RvZicfiss,
+    RvZiccif,
+    RvZiccrse,
+    RvZiccamoa,
+    RvZicclsm,
+    RvZic64b,
+    RvZmmul,
+    RvZaamo,
+    RvZalrsc,
+    RvZacas,
+    RvZabha,
+    RvZawrs,
+    RvZa64rs,
+    RvZa128rs,
+    RvZtso,
+    RvZfh,
+    RvZfhmin,
+    RvZfa,
+    RvZfbfmin,
+    RvZfinx,
+    RvZdinx,
+    RvZhinx,
+    RvZhinxmin,
+    RvZca,
+    RvZcb,
+    RvZcd,
+    RvZcf,
+    RvZcmop,
+    RvZba,
+    RvZbb,
+    RvZbc,
+    RvZbs,
+    RvZbkb,
+    RvZbkc,
+    RvZbkx,
+    RvZknd,
+    RvZkne,
+    RvZknh,
+    RvZksed,
+    RvZksh,
+    RvZkr,
+    RvZkt,
+    RvZve32x,
+    RvZve32f,
+    RvZve64x,
+    RvZve64f,
+    RvZve64d,
+    RvZvfh,
+    RvZvfhmin,
+    RvZvfbfmin,
+    RvZvfbfwma,
+    RvZvbb,
+    RvZvbc,
+    RvZvkb,
+    RvZvkg,
+    RvZvkned,
+    RvZvknha,
+    RvZvknhb,
+    RvZvksed,
+    RvZvksh,
+    RvZvkt,
+    RvSstc,
+    RvSscofpmf,
+    RvSvinval,
+    RvSvnapot,
+    RvSvpbmt,
+    RvSvadu,
+    RvSmaia,
+    RvSsaia,
+    RvSmstateen,
+    RvSsstateen,
+    RvSsnpm,
+    RvSupm,
+    RvXTheadVector,
 }
 /// Enum representing CPU information for the current architecture.
 ///
@@ -127,6 +353,20 @@ pub enum CpuInfo {
 }
 
 impl CpuInfo {
+    /// Returns the cache hierarchy of the CPU this info describes, ordered by level.
+    ///
+    /// Empty if neither CPUID nor the OS reports cache information.
+    pub fn caches(&self) -> &[cache::CacheInfo] {
+        match self {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            CpuInfo::X86(info) => &info.caches,
+            #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+            CpuInfo::Arm(info) => &info.caches,
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            CpuInfo::RiscV(info) => &info.caches,
+        }
+    }
+
     /// Checks if the CPU supports the given feature.
     ///
     /// # Arguments
@@ -233,6 +473,130 @@ impl CpuInfo {
                     InstructionSet::SHA1 => info.features.contains(ArmFeatures::SHA1),
                     InstructionSet::SHA2 => info.features.contains(ArmFeatures::SHA2),
                     InstructionSet::CRC32 => info.features.contains(ArmFeatures::CRC32),
+                    InstructionSet::FP => info.features.contains(ArmFeatures::FP),
+                    InstructionSet::EVTSTRM => info.features.contains(ArmFeatures::EVTSTRM),
+                    InstructionSet::ATOMICS => info.features.contains(ArmFeatures::ATOMICS),
+                    InstructionSet::FPHP => info.features.contains(ArmFeatures::FPHP),
+                    InstructionSet::ASIMDHP => info.features.contains(ArmFeatures::ASIMDHP),
+                    InstructionSet::CPUID => info.features.contains(ArmFeatures::CPUID),
+                    InstructionSet::ASIMDRDM => info.features.contains(ArmFeatures::ASIMDRDM),
+                    InstructionSet::JSCVT => info.features.contains(ArmFeatures::JSCVT),
+                    InstructionSet::FCMA => info.features.contains(ArmFeatures::FCMA),
+                    InstructionSet::LRCPC => info.features.contains(ArmFeatures::LRCPC),
+                    InstructionSet::DCPOP => info.features.contains(ArmFeatures::DCPOP),
+                    InstructionSet::SHA3 => info.features.contains(ArmFeatures::SHA3),
+                    InstructionSet::SM3 => info.features.contains(ArmFeatures::SM3),
+                    InstructionSet::SM4 => info.features.contains(ArmFeatures::SM4),
+                    InstructionSet::ASIMDDP => info.features.contains(ArmFeatures::ASIMDDP),
+                    InstructionSet::SHA512 => info.features.contains(ArmFeatures::SHA512),
+                    InstructionSet::SVE => info.features.contains(ArmFeatures::SVE),
+                    InstructionSet::ASIMDFHM => info.features.contains(ArmFeatures::ASIMDFHM),
+                    InstructionSet::DIT => info.features.contains(ArmFeatures::DIT),
+                    InstructionSet::USCAT => info.features.contains(ArmFeatures::USCAT),
+                    InstructionSet::ILRCPC => info.features.contains(ArmFeatures::ILRCPC),
+                    InstructionSet::FLAGM => info.features.contains(ArmFeatures::FLAGM),
+                    InstructionSet::SSBS => info.features.contains(ArmFeatures::SSBS),
+                    InstructionSet::SB => info.features.contains(ArmFeatures::SB),
+                    InstructionSet::PACA => info.features.contains(ArmFeatures::PACA),
+                    InstructionSet::PACG => info.features.contains(ArmFeatures::PACG),
+                    InstructionSet::GCS => info.features.contains(ArmFeatures::GCS),
+                    InstructionSet::CMPBR => info.features.contains(ArmFeatures::CMPBR),
+                    InstructionSet::FPRCVT => info.features.contains(ArmFeatures::FPRCVT),
+                    InstructionSet::F8MM8 => info.features.contains(ArmFeatures::F8MM8),
+                    InstructionSet::F8MM4 => info.features.contains(ArmFeatures::F8MM4),
+                    InstructionSet::SveF16mm => info.features.contains(ArmFeatures::SVE_F16MM),
+                    InstructionSet::SveEltperm => info.features.contains(ArmFeatures::SVE_ELTPERM),
+                    InstructionSet::SveAes2 => info.features.contains(ArmFeatures::SVE_AES2),
+                    InstructionSet::SveBfscale => info.features.contains(ArmFeatures::SVE_BFSCALE),
+                    InstructionSet::SVE2P2 => info.features.contains(ArmFeatures::SVE2P2),
+                    InstructionSet::SME2P2 => info.features.contains(ArmFeatures::SME2P2),
+                    InstructionSet::SmeSbitperm => {
+                        info.features.contains(ArmFeatures::SME_SBITPERM)
+                    }
+                    InstructionSet::SmeAes => info.features.contains(ArmFeatures::SME_AES),
+                    InstructionSet::SmeSfexpa => info.features.contains(ArmFeatures::SME_SFEXPA),
+                    InstructionSet::SmeStmop => info.features.contains(ArmFeatures::SME_STMOP),
+                    InstructionSet::SmeSmop4 => info.features.contains(ArmFeatures::SME_SMOP4),
+                    InstructionSet::DCPODP => info.features.contains(ArmFeatures::DCPODP),
+                    InstructionSet::SVE2 => info.features.contains(ArmFeatures::SVE2),
+                    InstructionSet::SVEAES => info.features.contains(ArmFeatures::SVEAES),
+                    InstructionSet::SVEPMULL => info.features.contains(ArmFeatures::SVEPMULL),
+                    InstructionSet::SVEBITPERM => info.features.contains(ArmFeatures::SVEBITPERM),
+                    InstructionSet::SVESHA3 => info.features.contains(ArmFeatures::SVESHA3),
+                    InstructionSet::SVESM4 => info.features.contains(ArmFeatures::SVESM4),
+                    InstructionSet::FLAGM2 => info.features.contains(ArmFeatures::FLAGM2),
+                    InstructionSet::FRINT => info.features.contains(ArmFeatures::FRINT),
+                    InstructionSet::SVEI8MM => info.features.contains(ArmFeatures::SVEI8MM),
+                    InstructionSet::SVEF32MM => info.features.contains(ArmFeatures::SVEF32MM),
+                    InstructionSet::SVEF64MM => info.features.contains(ArmFeatures::SVEF64MM),
+                    InstructionSet::SVEBF16 => info.features.contains(ArmFeatures::SVEBF16),
+                    InstructionSet::I8MM => info.features.contains(ArmFeatures::I8MM),
+                    InstructionSet::BF16 => info.features.contains(ArmFeatures::BF16),
+                    InstructionSet::DGH => info.features.contains(ArmFeatures::DGH),
+                    InstructionSet::RNG => info.features.contains(ArmFeatures::RNG),
+                    InstructionSet::BTI => info.features.contains(ArmFeatures::BTI),
+                    InstructionSet::MTE => info.features.contains(ArmFeatures::MTE),
+                    InstructionSet::ECV => info.features.contains(ArmFeatures::ECV),
+                    InstructionSet::AFP => info.features.contains(ArmFeatures::AFP),
+                    InstructionSet::RPRES => info.features.contains(ArmFeatures::RPRES),
+                    InstructionSet::MTE3 => info.features.contains(ArmFeatures::MTE3),
+                    InstructionSet::SME => info.features.contains(ArmFeatures::SME),
+                    InstructionSet::SmeI16i64 => info.features.contains(ArmFeatures::SME_I16I64),
+                    InstructionSet::SmeF64f64 => info.features.contains(ArmFeatures::SME_F64F64),
+                    InstructionSet::SmeI8i32 => info.features.contains(ArmFeatures::SME_I8I32),
+                    InstructionSet::SmeF16f32 => info.features.contains(ArmFeatures::SME_F16F32),
+                    InstructionSet::SmeB16f32 => info.features.contains(ArmFeatures::SME_B16F32),
+                    InstructionSet::SmeF32f32 => info.features.contains(ArmFeatures::SME_F32F32),
+                    InstructionSet::SmeFa64 => info.features.contains(ArmFeatures::SME_FA64),
+                    InstructionSet::WFXT => info.features.contains(ArmFeatures::WFXT),
+                    InstructionSet::EBF16 => info.features.contains(ArmFeatures::EBF16),
+                    InstructionSet::SveEbf16 => info.features.contains(ArmFeatures::SVE_EBF16),
+                    InstructionSet::CSSC => info.features.contains(ArmFeatures::CSSC),
+                    InstructionSet::RPRFM => info.features.contains(ArmFeatures::RPRFM),
+                    InstructionSet::SVE2P1 => info.features.contains(ArmFeatures::SVE2P1),
+                    InstructionSet::SME2 => info.features.contains(ArmFeatures::SME2),
+                    InstructionSet::SME2P1 => info.features.contains(ArmFeatures::SME2P1),
+                    InstructionSet::SmeI16i32 => info.features.contains(ArmFeatures::SME_I16I32),
+                    InstructionSet::SmeBi32i32 => info.features.contains(ArmFeatures::SME_BI32I32),
+                    InstructionSet::SmeB16b16 => info.features.contains(ArmFeatures::SME_B16B16),
+                    InstructionSet::SmeF16f16 => info.features.contains(ArmFeatures::SME_F16F16),
+                    InstructionSet::MOPS => info.features.contains(ArmFeatures::MOPS),
+                    InstructionSet::HBC => info.features.contains(ArmFeatures::HBC),
+                    InstructionSet::SveB16b16 => info.features.contains(ArmFeatures::SVE_B16B16),
+                    InstructionSet::LRCPC3 => info.features.contains(ArmFeatures::LRCPC3),
+                    InstructionSet::LSE128 => info.features.contains(ArmFeatures::LSE128),
+                    InstructionSet::FPMR => info.features.contains(ArmFeatures::FPMR),
+                    InstructionSet::LUT => info.features.contains(ArmFeatures::LUT),
+                    InstructionSet::FAMINMAX => info.features.contains(ArmFeatures::FAMINMAX),
+                    InstructionSet::F8CVT => info.features.contains(ArmFeatures::F8CVT),
+                    InstructionSet::F8FMA => info.features.contains(ArmFeatures::F8FMA),
+                    InstructionSet::F8DP4 => info.features.contains(ArmFeatures::F8DP4),
+                    InstructionSet::F8DP2 => info.features.contains(ArmFeatures::F8DP2),
+                    InstructionSet::F8E4M3 => info.features.contains(ArmFeatures::F8E4M3),
+                    InstructionSet::F8E5M2 => info.features.contains(ArmFeatures::F8E5M2),
+                    InstructionSet::SmeLutv2 => info.features.contains(ArmFeatures::SME_LUTV2),
+                    InstructionSet::SmeF8f16 => info.features.contains(ArmFeatures::SME_F8F16),
+                    InstructionSet::SmeF8f32 => info.features.contains(ArmFeatures::SME_F8F32),
+                    InstructionSet::SmeSf8fma => info.features.contains(ArmFeatures::SME_SF8FMA),
+                    InstructionSet::SmeSf8dp4 => info.features.contains(ArmFeatures::SME_SF8DP4),
+                    InstructionSet::SmeSf8dp2 => info.features.contains(ArmFeatures::SME_SF8DP2),
+                    InstructionSet::POE => info.features.contains(ArmFeatures::POE),
+                    InstructionSet::MteFar => info.features.contains(ArmFeatures::MTE_FAR),
+                    InstructionSet::MteStoreOnly => {
+                        info.features.contains(ArmFeatures::MTE_STORE_ONLY)
+                    }
+                    InstructionSet::SWP => info.features.contains(ArmFeatures::SWP),
+                    InstructionSet::HALF => info.features.contains(ArmFeatures::HALF),
+                    InstructionSet::THUMB => info.features.contains(ArmFeatures::THUMB),
+                    InstructionSet::FASTMULT => info.features.contains(ArmFeatures::FASTMULT),
+                    InstructionSet::VFP => info.features.contains(ArmFeatures::VFP),
+                    InstructionSet::VFPV3 => info.features.contains(ArmFeatures::VFPV3),
+                    InstructionSet::VFPV3D16 => info.features.contains(ArmFeatures::VFPV3D16),
+                    InstructionSet::VFPV4 => info.features.contains(ArmFeatures::VFPV4),
+                    InstructionSet::IDIVA => info.features.contains(ArmFeatures::IDIVA),
+                    InstructionSet::IDIVT => info.features.contains(ArmFeatures::IDIVT),
+                    InstructionSet::VFPD32 => info.features.contains(ArmFeatures::VFPD32),
+                    InstructionSet::LPAE => info.features.contains(ArmFeatures::LPAE),
                     _ => false,
                 }
             }
@@ -246,6 +610,100 @@ impl CpuInfo {
                     InstructionSet::RvF => info.features.contains(RiscVFeatures::F),
                     InstructionSet::RvD => info.features.contains(RiscVFeatures::D),
                     InstructionSet::RvC => info.features.contains(RiscVFeatures::C),
+                    InstructionSet::RvV => info.features.contains(RiscVFeatures::V),
+                    InstructionSet::RvH => info.features.contains(RiscVFeatures::H),
+                    InstructionSet::RvB => info.features.contains(RiscVFeatures::B),
+                    InstructionSet::RvQ => info.features.contains(RiscVFeatures::Q),
+                    InstructionSet::RvZicsr => info.features.contains(RiscVFeatures::ZICSR),
+                    InstructionSet::RvZifencei => info.features.contains(RiscVFeatures::ZIFENCEI),
+                    InstructionSet::RvZicntr => info.features.contains(RiscVFeatures::ZICNTR),
+                    InstructionSet::RvZihpm => info.features.contains(RiscVFeatures::ZIHPM),
+                    InstructionSet::RvZicbom => info.features.contains(RiscVFeatures::ZICBOM),
+                    InstructionSet::RvZicbop => info.features.contains(RiscVFeatures::ZICBOP),
+                    InstructionSet::RvZicboz => info.features.contains(RiscVFeatures::ZICBOZ),
+                    InstructionSet::RvZicond => info.features.contains(RiscVFeatures::ZICOND),
+                    InstructionSet::RvZihintpause => {
+                        info.features.contains(RiscVFeatures::ZIHINTPAUSE)
+                    }
+                    InstructionSet::RvZihintntl => info.features.contains(RiscVFeatures::ZIHINTNTL),
+                    InstructionSet::RvZimop => info.features.contains(RiscVFeatures::ZIMOP),
+                    InstructionSet::RvZicfilp => info.features.contains(RiscVFeatures::ZICFILP),
+                    InstructionSet::RvZicfiss => info.features.contains(RiscVFeatures::ZICFISS),
+                    InstructionSet::RvZiccif => info.features.contains(RiscVFeatures::ZICCIF),
+                    InstructionSet::RvZiccrse => info.features.contains(RiscVFeatures::ZICCRSE),
+                    InstructionSet::RvZiccamoa => info.features.contains(RiscVFeatures::ZICCAMOA),
+                    InstructionSet::RvZicclsm => info.features.contains(RiscVFeatures::ZICCLSM),
+                    InstructionSet::RvZic64b => info.features.contains(RiscVFeatures::ZIC64B),
+                    InstructionSet::RvZmmul => info.features.contains(RiscVFeatures::ZMMUL),
+                    InstructionSet::RvZaamo => info.features.contains(RiscVFeatures::ZAAMO),
+                    InstructionSet::RvZalrsc => info.features.contains(RiscVFeatures::ZALRSC),
+                    InstructionSet::RvZacas => info.features.contains(RiscVFeatures::ZACAS),
+                    InstructionSet::RvZabha => info.features.contains(RiscVFeatures::ZABHA),
+                    InstructionSet::RvZawrs => info.features.contains(RiscVFeatures::ZAWRS),
+                    InstructionSet::RvZa64rs => info.features.contains(RiscVFeatures::ZA64RS),
+                    InstructionSet::RvZa128rs => info.features.contains(RiscVFeatures::ZA128RS),
+                    InstructionSet::RvZtso => info.features.contains(RiscVFeatures::ZTSO),
+                    InstructionSet::RvZfh => info.features.contains(RiscVFeatures::ZFH),
+                    InstructionSet::RvZfhmin => info.features.contains(RiscVFeatures::ZFHMIN),
+                    InstructionSet::RvZfa => info.features.contains(RiscVFeatures::ZFA),
+                    InstructionSet::RvZfbfmin => info.features.contains(RiscVFeatures::ZFBFMIN),
+                    InstructionSet::RvZfinx => info.features.contains(RiscVFeatures::ZFINX),
+                    InstructionSet::RvZdinx => info.features.contains(RiscVFeatures::ZDINX),
+                    InstructionSet::RvZhinx => info.features.contains(RiscVFeatures::ZHINX),
+                    InstructionSet::RvZhinxmin => info.features.contains(RiscVFeatures::ZHINXMIN),
+                    InstructionSet::RvZca => info.features.contains(RiscVFeatures::ZCA),
+                    InstructionSet::RvZcb => info.features.contains(RiscVFeatures::ZCB),
+                    InstructionSet::RvZcd => info.features.contains(RiscVFeatures::ZCD),
+                    InstructionSet::RvZcf => info.features.contains(RiscVFeatures::ZCF),
+                    InstructionSet::RvZcmop => info.features.contains(RiscVFeatures::ZCMOP),
+                    InstructionSet::RvZba => info.features.contains(RiscVFeatures::ZBA),
+                    InstructionSet::RvZbb => info.features.contains(RiscVFeatures::ZBB),
+                    InstructionSet::RvZbc => info.features.contains(RiscVFeatures::ZBC),
+                    InstructionSet::RvZbs => info.features.contains(RiscVFeatures::ZBS),
+                    InstructionSet::RvZbkb => info.features.contains(RiscVFeatures::ZBKB),
+                    InstructionSet::RvZbkc => info.features.contains(RiscVFeatures::ZBKC),
+                    InstructionSet::RvZbkx => info.features.contains(RiscVFeatures::ZBKX),
+                    InstructionSet::RvZknd => info.features.contains(RiscVFeatures::ZKND),
+                    InstructionSet::RvZkne => info.features.contains(RiscVFeatures::ZKNE),
+                    InstructionSet::RvZknh => info.features.contains(RiscVFeatures::ZKNH),
+                    InstructionSet::RvZksed => info.features.contains(RiscVFeatures::ZKSED),
+                    InstructionSet::RvZksh => info.features.contains(RiscVFeatures::ZKSH),
+                    InstructionSet::RvZkr => info.features.contains(RiscVFeatures::ZKR),
+                    InstructionSet::RvZkt => info.features.contains(RiscVFeatures::ZKT),
+                    InstructionSet::RvZve32x => info.features.contains(RiscVFeatures::ZVE32X),
+                    InstructionSet::RvZve32f => info.features.contains(RiscVFeatures::ZVE32F),
+                    InstructionSet::RvZve64x => info.features.contains(RiscVFeatures::ZVE64X),
+                    InstructionSet::RvZve64f => info.features.contains(RiscVFeatures::ZVE64F),
+                    InstructionSet::RvZve64d => info.features.contains(RiscVFeatures::ZVE64D),
+                    InstructionSet::RvZvfh => info.features.contains(RiscVFeatures::ZVFH),
+                    InstructionSet::RvZvfhmin => info.features.contains(RiscVFeatures::ZVFHMIN),
+                    InstructionSet::RvZvfbfmin => info.features.contains(RiscVFeatures::ZVFBFMIN),
+                    InstructionSet::RvZvfbfwma => info.features.contains(RiscVFeatures::ZVFBFWMA),
+                    InstructionSet::RvZvbb => info.features.contains(RiscVFeatures::ZVBB),
+                    InstructionSet::RvZvbc => info.features.contains(RiscVFeatures::ZVBC),
+                    InstructionSet::RvZvkb => info.features.contains(RiscVFeatures::ZVKB),
+                    InstructionSet::RvZvkg => info.features.contains(RiscVFeatures::ZVKG),
+                    InstructionSet::RvZvkned => info.features.contains(RiscVFeatures::ZVKNED),
+                    InstructionSet::RvZvknha => info.features.contains(RiscVFeatures::ZVKNHA),
+                    InstructionSet::RvZvknhb => info.features.contains(RiscVFeatures::ZVKNHB),
+                    InstructionSet::RvZvksed => info.features.contains(RiscVFeatures::ZVKSED),
+                    InstructionSet::RvZvksh => info.features.contains(RiscVFeatures::ZVKSH),
+                    InstructionSet::RvZvkt => info.features.contains(RiscVFeatures::ZVKT),
+                    InstructionSet::RvSstc => info.features.contains(RiscVFeatures::SSTC),
+                    InstructionSet::RvSscofpmf => info.features.contains(RiscVFeatures::SSCOFPMF),
+                    InstructionSet::RvSvinval => info.features.contains(RiscVFeatures::SVINVAL),
+                    InstructionSet::RvSvnapot => info.features.contains(RiscVFeatures::SVNAPOT),
+                    InstructionSet::RvSvpbmt => info.features.contains(RiscVFeatures::SVPBMT),
+                    InstructionSet::RvSvadu => info.features.contains(RiscVFeatures::SVADU),
+                    InstructionSet::RvSmaia => info.features.contains(RiscVFeatures::SMAIA),
+                    InstructionSet::RvSsaia => info.features.contains(RiscVFeatures::SSAIA),
+                    InstructionSet::RvSmstateen => info.features.contains(RiscVFeatures::SMSTATEEN),
+                    InstructionSet::RvSsstateen => info.features.contains(RiscVFeatures::SSSTATEEN),
+                    InstructionSet::RvSsnpm => info.features.contains(RiscVFeatures::SSNPM),
+                    InstructionSet::RvSupm => info.features.contains(RiscVFeatures::SUPM),
+                    InstructionSet::RvXTheadVector => {
+                        info.features.contains(RiscVFeatures::XTHEADVECTOR)
+                    }
                     _ => false,
                 }
             }
@@ -0,0 +1,135 @@
+// src/numa.rs
+//! NUMA node layout read from Linux sysfs.
+//!
+//! Each node under `/sys/devices/system/node/` contributes its CPU list,
+//! its distance row and its memory totals.
+
+use crate::sysfs::{self, Source};
+
+/// Root of the NUMA node sysfs hierarchy.
+pub const NODE_ROOT: &str = "/sys/devices/system/node";
+
+/// A single NUMA node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumaNode {
+    /// Node ID
+    pub id: usize,
+    /// OS CPU IDs local to this node (empty for memory-only nodes)
+    pub cpus: Vec<usize>,
+    /// Relative access distance to every online node, indexed like [`NumaTopology::online`]
+    pub distances: Vec<u32>,
+    /// Total memory attached to this node, in KiB
+    pub mem_total_kb: Option<u64>,
+    /// Free memory on this node, in KiB
+    pub mem_free_kb: Option<u64>,
+}
+
+/// All online NUMA nodes, sorted by ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumaTopology {
+    /// Online nodes that could be read
+    pub nodes: Vec<NumaNode>,
+    /// IDs of every online node, in the order of each distance row
+    pub online: Vec<usize>,
+}
+
+impl NumaTopology {
+    /// Returns the node with the given ID.
+    pub fn node(&self, id: usize) -> Option<&NumaNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Returns the ID of the node a CPU belongs to.
+    pub fn node_of_cpu(&self, cpu_id: usize) -> Option<usize> {
+        self.nodes
+            .iter()
+            .find(|n| n.cpus.contains(&cpu_id))
+            .map(|n| n.id)
+    }
+
+    /// Returns the CPUs local to a node.
+    pub fn cpus_of_node(&self, id: usize) -> Option<&[usize]> {
+        self.node(id).map(|n| n.cpus.as_slice())
+    }
+
+    /// Returns the kernel's distance from node `from` to node `to`.
+    pub fn distance(&self, from: usize, to: usize) -> Option<u32> {
+        let idx = self.online.iter().position(|&id| id == to)?;
+        self.node(from)?.distances.get(idx).copied()
+    }
+}
+
+/// Extracts `MemTotal` and `MemFree` (in KiB) from a node `meminfo` file.
+pub fn parse_node_meminfo(s: &str) -> (Option<u64>, Option<u64>) {
+    let mut total = None;
+    let mut free = None;
+    for line in s.lines() {
+        // Lines look like "Node 0 MemTotal:       5471992 kB"
+        let Some((key, val)) = line.split_once(':') else {
+            continue;
+        };
+        let val = val.split_whitespace().next().and_then(|v| v.parse().ok());
+        if key.ends_with("MemTotal") {
+            total = val;
+        } else if key.ends_with("MemFree") {
+            free = val;
+        }
+    }
+    (total, free)
+}
+
+/// Reads a single NUMA node.
+fn read_node(src: &Source, id: usize) -> Option<NumaNode> {
+    let dir = format!("{}/node{}", NODE_ROOT, id);
+    let cpus = sysfs::parse_cpu_list(&src.read_trimmed(&format!("{}/cpulist", dir))?);
+    let distances = src
+        .read_trimmed(&format!("{}/distance", dir))
+        .map(|s| {
+            s.split_whitespace()
+                .filter_map(|d| d.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    let (mem_total_kb, mem_free_kb) = src
+        .read_trimmed(&format!("{}/meminfo", dir))
+        .map(|s| parse_node_meminfo(&s))
+        .unwrap_or((None, None));
+    Some(NumaNode {
+        id,
+        cpus,
+        distances,
+        mem_total_kb,
+        mem_free_kb,
+    })
+}
+
+/// Reads every online NUMA node on the running system.
+pub fn read() -> Option<NumaTopology> {
+    read_from(&Source::live())
+}
+
+/// Reads every online NUMA node.
+/// Returns `None` if the kernel does not expose NUMA information.
+pub fn read_from(src: &Source) -> Option<NumaTopology> {
+    let online = sysfs::parse_cpu_list(&src.read_trimmed(&format!("{}/online", NODE_ROOT))?);
+    let nodes: Vec<NumaNode> = online.iter().filter_map(|&id| read_node(src, id)).collect();
+    if nodes.is_empty() {
+        return None;
+    }
+    Some(NumaTopology { nodes, online })
+}
+
+/// Returns the NUMA node of a single CPU on the running system.
+pub fn node_of_cpu(cpu_id: usize) -> Option<usize> {
+    node_of_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Returns the NUMA node of a single CPU from its `cpuN/nodeM` sysfs link,
+/// falling back to searching every node's `cpulist`.
+pub fn node_of_cpu_from(src: &Source, cpu_id: usize) -> Option<usize> {
+    let dir = format!("{}/cpu{}", sysfs::CPU_ROOT, cpu_id);
+    src.list_dir(&dir)
+        .iter()
+        .find_map(|name| name.strip_prefix("node")?.parse().ok())
+        .or_else(|| read_from(src)?.node_of_cpu(cpu_id))
+}
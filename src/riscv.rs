@@ -1,15 +1,33 @@
 #![allow(dead_code)]
+use crate::cache::{self, CacheInfo};
+use crate::cpufreq::{self, CpuFreq};
+use crate::sysfs::{Source, CPU_ROOT};
+use crate::{numa, topology};
+use bitflags::bitflags;
 use core::fmt;
-#[cfg(any(target_os = "linux", not(target_os = "linux")))]
-use libc::{sysconf, _SC_NPROCESSORS_ONLN};
-#[cfg(target_os = "linux")]
-use std::fs;
+
+/// `riscv_hwprobe` syscall backend
+pub mod hwprobe;
+/// Machine ID decoding and the vendor/core database
+pub mod ids;
+/// ISA string parsing
+pub mod isa;
+/// RVA/RVB profile compliance
+pub mod profile;
+/// Vector unit detection
+pub mod vector;
+
+use hwprobe::HwProbe;
+use ids::MachineIds;
+use isa::Isa;
+use vector::{VectorInfo, VectorKind};
 
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
     /// Packed feature flags for RISC-V.
-    /// Each flag represents a supported extension in the RISC-V ISA.
-    pub struct RiscVFeatures: u32 {
+    /// Each flag represents a supported extension in the RISC-V ISA and is
+    /// named after the extension in upper case.
+    pub struct RiscVFeatures: u128 {
         /// Base integer ISA
         const I = 1 << 0;
         /// Integer multiply/divide
@@ -22,13 +40,195 @@ bitflags! {
         const D = 1 << 4;
         /// Compressed instructions
         const C = 1 << 5;
+        /// Vector extension
+        const V = 1 << 6;
+        /// Hypervisor extension
+        const H = 1 << 7;
+        /// Bit manipulation (Zba, Zbb and Zbs)
+        const B = 1 << 8;
+        /// Quad-precision floating point
+        const Q = 1 << 9;
+        /// Control and status register instructions
+        const ZICSR = 1 << 10;
+        /// Instruction-fetch fence
+        const ZIFENCEI = 1 << 11;
+        /// Base counters and timers
+        const ZICNTR = 1 << 12;
+        /// Hardware performance counters
+        const ZIHPM = 1 << 13;
+        /// Cache-block management instructions
+        const ZICBOM = 1 << 14;
+        /// Cache-block prefetch instructions
+        const ZICBOP = 1 << 15;
+        /// Cache-block zero instructions
+        const ZICBOZ = 1 << 16;
+        /// Integer conditional operations
+        const ZICOND = 1 << 17;
+        /// Pause hint
+        const ZIHINTPAUSE = 1 << 18;
+        /// Non-temporal locality hints
+        const ZIHINTNTL = 1 << 19;
+        /// May-be-operations
+        const ZIMOP = 1 << 20;
+        /// Control-flow integrity landing pads
+        const ZICFILP = 1 << 21;
+        /// Control-flow integrity shadow stack
+        const ZICFISS = 1 << 22;
+        /// Instruction fetch atomicity for main memory
+        const ZICCIF = 1 << 23;
+        /// Main memory supports reservation set extensions
+        const ZICCRSE = 1 << 24;
+        /// Main memory supports all atomics in A
+        const ZICCAMOA = 1 << 25;
+        /// Misaligned loads and stores to main memory
+        const ZICCLSM = 1 << 26;
+        /// 64-byte cache blocks
+        const ZIC64B = 1 << 27;
+        /// Multiply without divide
+        const ZMMUL = 1 << 28;
+        /// Atomic memory operations
+        const ZAAMO = 1 << 29;
+        /// Load-reserved/store-conditional
+        const ZALRSC = 1 << 30;
+        /// Atomic compare-and-swap
+        const ZACAS = 1 << 31;
+        /// Byte and halfword atomics
+        const ZABHA = 1 << 32;
+        /// Wait-on-reservation-set
+        const ZAWRS = 1 << 33;
+        /// Reservation sets of at most 64 bytes
+        const ZA64RS = 1 << 34;
+        /// Reservation sets of at most 128 bytes
+        const ZA128RS = 1 << 35;
+        /// Total store ordering
+        const ZTSO = 1 << 36;
+        /// Half-precision floating point
+        const ZFH = 1 << 37;
+        /// Minimal half-precision floating point
+        const ZFHMIN = 1 << 38;
+        /// Additional floating-point instructions
+        const ZFA = 1 << 39;
+        /// Scalar BFloat16 conversions
+        const ZFBFMIN = 1 << 40;
+        /// Single-precision floating point in integer registers
+        const ZFINX = 1 << 41;
+        /// Double-precision floating point in integer registers
+        const ZDINX = 1 << 42;
+        /// Half-precision floating point in integer registers
+        const ZHINX = 1 << 43;
+        /// Minimal half-precision floating point in integer registers
+        const ZHINXMIN = 1 << 44;
+        /// Compressed instructions without floating-point loads and stores
+        const ZCA = 1 << 45;
+        /// Additional compressed instructions
+        const ZCB = 1 << 46;
+        /// Compressed double-precision loads and stores
+        const ZCD = 1 << 47;
+        /// Compressed single-precision loads and stores
+        const ZCF = 1 << 48;
+        /// Compressed may-be-operations
+        const ZCMOP = 1 << 49;
+        /// Address generation
+        const ZBA = 1 << 50;
+        /// Basic bit manipulation
+        const ZBB = 1 << 51;
+        /// Carry-less multiplication
+        const ZBC = 1 << 52;
+        /// Single-bit instructions
+        const ZBS = 1 << 53;
+        /// Bit manipulation for cryptography
+        const ZBKB = 1 << 54;
+        /// Carry-less multiplication for cryptography
+        const ZBKC = 1 << 55;
+        /// Crossbar permutations
+        const ZBKX = 1 << 56;
+        /// NIST AES decryption
+        const ZKND = 1 << 57;
+        /// NIST AES encryption
+        const ZKNE = 1 << 58;
+        /// NIST SHA-2 hashing
+        const ZKNH = 1 << 59;
+        /// ShangMi SM4 block cipher
+        const ZKSED = 1 << 60;
+        /// ShangMi SM3 hashing
+        const ZKSH = 1 << 61;
+        /// Entropy source
+        const ZKR = 1 << 62;
+        /// Data-independent execution latency
+        const ZKT = 1 << 63;
+        /// Embedded vector, 32-bit integer elements
+        const ZVE32X = 1 << 64;
+        /// Embedded vector, 32-bit floating-point elements
+        const ZVE32F = 1 << 65;
+        /// Embedded vector, 64-bit integer elements
+        const ZVE64X = 1 << 66;
+        /// Embedded vector, 64-bit integer and single-precision elements
+        const ZVE64F = 1 << 67;
+        /// Embedded vector, 64-bit integer and double-precision elements
+        const ZVE64D = 1 << 68;
+        /// Vector half-precision floating point
+        const ZVFH = 1 << 69;
+        /// Minimal vector half-precision floating point
+        const ZVFHMIN = 1 << 70;
+        /// Vector BFloat16 conversions
+        const ZVFBFMIN = 1 << 71;
+        /// Vector BFloat16 widening multiply-add
+        const ZVFBFWMA = 1 << 72;
+        /// Vector basic bit manipulation
+        const ZVBB = 1 << 73;
+        /// Vector carry-less multiplication
+        const ZVBC = 1 << 74;
+        /// Vector bit manipulation for cryptography
+        const ZVKB = 1 << 75;
+        /// Vector GCM/GMAC
+        const ZVKG = 1 << 76;
+        /// Vector NIST AES block cipher
+        const ZVKNED = 1 << 77;
+        /// Vector NIST SHA-2 (SHA-256)
+        const ZVKNHA = 1 << 78;
+        /// Vector NIST SHA-2 (SHA-256 and SHA-512)
+        const ZVKNHB = 1 << 79;
+        /// Vector ShangMi SM4 block cipher
+        const ZVKSED = 1 << 80;
+        /// Vector ShangMi SM3 hashing
+        const ZVKSH = 1 << 81;
+        /// Vector data-independent execution latency
+        const ZVKT = 1 << 82;
+        /// Supervisor-mode timer interrupts
+        const SSTC = 1 << 83;
+        /// Counter overflow and mode-based filtering
+        const SSCOFPMF = 1 << 84;
+        /// Fine-grained address-translation cache invalidation
+        const SVINVAL = 1 << 85;
+        /// NAPOT translation contiguity
+        const SVNAPOT = 1 << 86;
+        /// Page-based memory types
+        const SVPBMT = 1 << 87;
+        /// Hardware updating of A/D bits
+        const SVADU = 1 << 88;
+        /// Advanced interrupt architecture (machine mode)
+        const SMAIA = 1 << 89;
+        /// Advanced interrupt architecture (supervisor mode)
+        const SSAIA = 1 << 90;
+        /// State enable (machine mode)
+        const SMSTATEEN = 1 << 91;
+        /// State enable (supervisor mode)
+        const SSSTATEEN = 1 << 92;
+        /// Pointer masking (supervisor mode)
+        const SSNPM = 1 << 93;
+        /// Pointer masking (user mode)
+        const SUPM = 1 << 94;
+        /// T-Head pre-ratification vector extension (RVV 0.7.1), binary-incompatible with `V`
+        const XTHEADVECTOR = 1 << 95;
     }
 }
 
 /// Stores information about a single logical RISC-V CPU.
 /// Includes vendor, brand string, feature flags, core/thread counts.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct RiscVCpuInfo {
+    /// OS logical CPU ID this info describes
+    pub cpu_id: usize,
     /// CPU vendor string (e.g., "SiFive")
     pub vendor: String,
     /// CPU brand string or ISA string
@@ -39,83 +239,261 @@ pub struct RiscVCpuInfo {
     pub cores: u32,
     /// Number of threads per core (usually 1 for RISC-V)
     pub threads_per_core: u32,
+    /// Cache hierarchy of the CPU this was gathered on, ordered by level
+    pub caches: Vec<CacheInfo>,
+    /// NUMA node the hart belongs to, if the OS reports one
+    pub numa_node: Option<usize>,
+    /// Parsed ISA string, if the OS reports one
+    pub isa: Option<Isa>,
+    /// `riscv_hwprobe` answers for this hart (Linux 6.4+)
+    pub hwprobe: Option<HwProbe>,
+    /// Vector unit, if the hart has one
+    pub vector: Option<VectorInfo>,
+    /// mvendorid/marchid/mimpid, if the OS reports them
+    pub ids: Option<MachineIds>,
+}
+
+impl RiscVCpuInfo {
+    /// Register width of the hart: from the ISA string, or else the width this
+    /// crate was built for, which is the running hart's on the hwprobe and
+    /// bare-metal paths. `None` for ISA-less captures analysed off-target.
+    pub fn xlen(&self) -> Option<u32> {
+        if let Some(isa) = &self.isa {
+            Some(isa.xlen)
+        } else if cfg!(target_arch = "riscv64") {
+            Some(64)
+        } else if cfg!(target_arch = "riscv32") {
+            Some(32)
+        } else {
+            None
+        }
+    }
+
+    /// Evaluates the feature flags against the RVI20/RVA/RVB23 64-bit profiles.
+    /// Returns `None` unless [`Self::xlen`] says the hart is RV64.
+    pub fn profile(&self) -> Option<profile::ProfileReport> {
+        (self.xlen() == Some(64)).then(|| profile::infer(self.features))
+    }
+
+    /// Reads the current cpufreq state of this hart from the live system, even if
+    /// this info was built from a captured tree; use [`Self::frequency_from`] for those.
+    /// Not cached, since the current frequency and governor change at runtime.
+    pub fn frequency(&self) -> Option<CpuFreq> {
+        cpufreq::read_cpu(self.cpu_id)
+    }
+
+    /// Reads the cpufreq state of this hart from `src`.
+    pub fn frequency_from(&self, src: &Source) -> Option<CpuFreq> {
+        cpufreq::read_cpu_from(src, self.cpu_id)
+    }
+}
+
+impl RiscVFeatures {
+    /// Returns the flags for one extension name (case-insensitive), expanding
+    /// shorthands such as `b`, `zkn` and `zvkng` into their components.
+    pub fn from_extension(name: &str) -> RiscVFeatures {
+        use RiscVFeatures as F;
+        let zkn = F::ZBKB | F::ZBKC | F::ZBKX | F::ZKNE | F::ZKND | F::ZKNH;
+        let zks = F::ZBKB | F::ZBKC | F::ZBKX | F::ZKSED | F::ZKSH;
+        let zvkn = F::ZVKNED | F::ZVKNHB | F::ZVKB | F::ZVKT;
+        let zvks = F::ZVKSED | F::ZVKSH | F::ZVKB | F::ZVKT;
+        match name.to_ascii_lowercase().as_str() {
+            "b" => F::B | F::ZBA | F::ZBB | F::ZBS,
+            "zk" => zkn | F::ZKR | F::ZKT,
+            "zkn" => zkn,
+            "zks" => zks,
+            "zvkn" => zvkn,
+            "zvknc" => zvkn | F::ZVBC,
+            "zvkng" => zvkn | F::ZVKG,
+            "zvks" => zvks,
+            "zvksc" => zvks | F::ZVBC,
+            "zvksg" => zvks | F::ZVKG,
+            other => F::all()
+                .iter_names()
+                .find(|(flag, _)| flag.eq_ignore_ascii_case(other))
+                .map_or(F::empty(), |(_, f)| f),
+        }
+    }
+
+    /// Collects the flags for the extensions of a parsed ISA string.
+    pub fn from_isa(isa: &Isa) -> RiscVFeatures {
+        isa.extensions
+            .iter()
+            .fold(RiscVFeatures::empty(), |acc, ext| {
+                acc | RiscVFeatures::from_extension(&ext.name)
+            })
+    }
+
+    /// Decodes the single-letter extension bits of the `misa` CSR (bit 0 = `A`, ..., bit 25 = `Z`).
+    pub fn from_misa(misa: u64) -> RiscVFeatures {
+        (b'a'..=b'z')
+            .enumerate()
+            .filter(|&(bit, _)| misa & (1 << bit) != 0)
+            .fold(RiscVFeatures::empty(), |acc, (_, letter)| {
+                acc | RiscVFeatures::from_extension(&(letter as char).to_string())
+            })
+    }
+}
+
+/// `COMPAT_HWCAP_ISA_V` in `AT_HWCAP`: the kernel enabled the vector unit for user mode.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+const HWCAP_V: libc::c_ulong = 1 << (b'V' - b'A');
+
+/// Path of a device-tree property of the node describing CPU `cpu_id`.
+fn of_node(cpu_id: usize, property: &str) -> String {
+    format!("{}/cpu{}/of_node/{}", CPU_ROOT, cpu_id, property)
+}
+
+/// Builds RISC-V CPU information for hart `cpu_id` from procfs/sysfs files only.
+/// Works on any host, so captured trees can be analysed off-target.
+pub fn gather_from(src: &Source, cpu_id: usize) -> RiscVCpuInfo {
+    let entries = src.proc_cpuinfo();
+    let entry = entries
+        .iter()
+        .find(|e| e.processor == Some(cpu_id))
+        .or_else(|| entries.first());
+    let isa_line = entry
+        .and_then(|e| e.get("isa"))
+        .unwrap_or_default()
+        .to_string();
+    // The IDs name the core itself; the cpuinfo strings are only a fallback
+    let ids = entry.and_then(MachineIds::from_proc_entry);
+    let (vendor_name, core_name) = ids::name_cpu_from(src, cpu_id, ids);
+    let vendor =
+        vendor_name.unwrap_or_else(|| entry.and_then(|e| e.get("vendor")).unwrap_or_default());
+    let brand = core_name.map_or_else(|| isa_line.clone(), str::to_string);
+    let isa = Isa::parse(&isa_line);
+    let features = isa
+        .as_ref()
+        .map_or(RiscVFeatures::empty(), RiscVFeatures::from_isa);
+
+    // Topology: prefer sysfs, which knows about SMT and multiple sockets
+    let (cores, threads_per_core) = match topology::read_from(src) {
+        Some(topo) => (topo.core_count() as u32, topo.threads_per_core() as u32),
+        None => {
+            let n = src
+                .online_cpus()
+                .map_or(entries.len(), |cpus| cpus.len())
+                .max(1);
+            (n as u32, 1)
+        }
+    };
+
+    let mut info = RiscVCpuInfo {
+        cpu_id,
+        vendor: vendor.to_string(),
+        brand,
+        features,
+        cores,
+        threads_per_core,
+        caches: cache::read_cpu_from(src, cpu_id),
+        numa_node: numa::node_of_cpu_from(src, cpu_id),
+        isa,
+        hwprobe: None,
+        vector: None,
+        ids,
+    };
+    apply_vector(src, cpu_id, &mut info);
+    info
+}
+
+/// Classifies the vector unit and keeps the flags consistent with it: an RVV 0.7.1
+/// unit reported as `V` moves over to [`RiscVFeatures::XTHEADVECTOR`], so `V` only
+/// ever means RVV 1.0.
+fn apply_vector(src: &Source, cpu_id: usize, info: &mut RiscVCpuInfo) {
+    info.vector = vector::detect_from(
+        src,
+        cpu_id,
+        info.features,
+        info.isa.as_ref(),
+        info.hwprobe.as_ref(),
+    );
+    match info.vector.map(|v| v.kind) {
+        Some(VectorKind::XTheadVector) => {
+            info.features.remove(vector::rvv1_0_features());
+            info.features.insert(RiscVFeatures::XTHEADVECTOR);
+        }
+        Some(VectorKind::Rvv1_0) => info.features.remove(RiscVFeatures::XTHEADVECTOR),
+        None => (),
+    }
 }
 
 /// Gathers RISC-V CPU info for the current system.
-/// Parses `/proc/cpuinfo` on Linux, or reads the misa CSR on bare-metal.
+/// On Linux, prefers `riscv_hwprobe` and falls back to `/proc/cpuinfo`; on bare-metal,
+/// reads the misa CSR.
 /// Returns a `RiscVCpuInfo` struct with vendor, brand, features, core/thread counts.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
 pub fn gather() -> RiscVCpuInfo {
-    // Vendor & ISA parsing
-    let (vendor, brand, features) = {
-        #[cfg(target_os = "linux")]
-        {
-            let info = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
-            let mut vendor = String::new();
-            let mut isa_line = String::new();
-            for line in info.lines() {
-                if line.starts_with("vendor\t:") {
-                    if let Some(val) = line.split(':').nth(1) {
-                        vendor = val.trim().to_string();
-                    }
-                }
-                if line.starts_with("isa\t:") {
-                    if let Some(val) = line.split(':').nth(1) {
-                        isa_line = val.trim().to_string();
-                    }
+    #[cfg(target_os = "linux")]
+    {
+        let src = Source::live();
+        let cpu_id = unsafe { libc::sched_getcpu() }.max(0) as usize;
+        let mut info = gather_from(&src, cpu_id);
+        if let Some(hw) = hwprobe::probe_cpu(cpu_id) {
+            // The ISA string also lists extensions an older kernel has no hwprobe bit for
+            info.features = hw.merge_features(info.features);
+            if let Some(hw_ids) = MachineIds::from_hwprobe(&hw) {
+                info.ids = Some(hw_ids);
+                let (vendor, core) = ids::name_cpu_from(&src, cpu_id, info.ids);
+                if let Some(v) = vendor {
+                    info.vendor = v.to_string();
                 }
-            }
-            let mut feats = RiscVFeatures::empty();
-            for token in isa_line.split('_').flat_map(|s| s.split('v')) {
-                match token {
-                    "i" => feats.insert(RiscVFeatures::I),
-                    "m" => feats.insert(RiscVFeatures::M),
-                    "a" => feats.insert(RiscVFeatures::A),
-                    "f" => feats.insert(RiscVFeatures::F),
-                    "d" => feats.insert(RiscVFeatures::D),
-                    "c" => feats.insert(RiscVFeatures::C),
-                    _ => (),
+                if let Some(c) = core {
+                    info.brand = c.to_string();
                 }
             }
-            (vendor, isa_line, feats)
+            info.hwprobe = Some(hw);
+            apply_vector(&src, cpu_id, &mut info);
         }
-        #[cfg(not(target_os = "linux"))]
-        unsafe {
-            // Bare-metal: read misa CSR
-            let misa: usize;
-            core::arch::asm!("csrr {0}, misa", out(reg) misa);
-            let mut feats = RiscVFeatures::empty();
-            if misa & (1 << 0) != 0 {
-                feats.insert(RiscVFeatures::I);
+        // An ISA-string `v` alone does not mean user-mode V is enabled (kernels before
+        // 6.5, or a 0.7.1 unit without a device-tree `compatible`), and `vlenb` would trap
+        let hwprobe_v = info
+            .hwprobe
+            .as_ref()
+            .is_some_and(|hw| hw.features.intersects(vector::rvv1_0_features()));
+        let hwcap_v = unsafe { libc::getauxval(libc::AT_HWCAP) } & HWCAP_V != 0;
+        if let Some(v) = info.vector.as_mut() {
+            if v.kind == VectorKind::Rvv1_0 && v.vlen.is_none() && (hwprobe_v || hwcap_v) {
+                v.vlen = Some(vector::read_vlenb() * 8);
             }
-            if misa & (1 << 1) != 0 {
-                feats.insert(RiscVFeatures::M);
-            }
-            if misa & (1 << 2) != 0 {
-                feats.insert(RiscVFeatures::A);
-            }
-            if misa & (1 << 3) != 0 {
-                feats.insert(RiscVFeatures::F);
-            }
-            if misa & (1 << 4) != 0 {
-                feats.insert(RiscVFeatures::D);
-            }
-            if misa & (1 << 8) != 0 {
-                feats.insert(RiscVFeatures::C);
+        }
+        info
+    }
+    #[cfg(not(target_os = "linux"))]
+    unsafe {
+        // Bare-metal: read misa and the machine ID CSRs
+        let misa: usize;
+        core::arch::asm!("csrr {0}, misa", out(reg) misa);
+        let feats = RiscVFeatures::from_misa(misa as u64);
+        let ids = MachineIds::read();
+        let hex = |id: u64| {
+            if id == 0 {
+                String::new()
+            } else {
+                format!("0x{:x}", id)
             }
-            (String::new(), String::new(), feats)
+        };
+        RiscVCpuInfo {
+            cpu_id: 0,
+            vendor: ids
+                .vendor_name()
+                .map_or_else(|| hex(ids.mvendorid), str::to_string),
+            brand: ids
+                .core_name()
+                .map_or_else(|| hex(ids.marchid), str::to_string),
+            features: feats,
+            cores: libc::sysconf(libc::_SC_NPROCESSORS_ONLN).max(1) as u32,
+            threads_per_core: 1,
+            caches: Vec::new(),
+            numa_node: None,
+            isa: None,
+            hwprobe: None,
+            vector: None,
+            ids: Some(ids),
         }
-    };
-
-    // Topology
-    let cores = unsafe { sysconf(_SC_NPROCESSORS_ONLN) as u32 };
-    let threads_per_core = 1;
-
-    RiscVCpuInfo {
-        vendor,
-        brand,
-        features,
-        cores,
-        threads_per_core,
     }
 }
 
@@ -0,0 +1,304 @@
+// src/riscv/hwprobe.rs
+//! Linux `riscv_hwprobe` (6.4+) support.
+//!
+//! [`HwProbe::from_pairs`] decodes recorded key/value pairs on any host;
+//! [`probe`] and [`probe_cpu`] issue the syscall on RISC-V Linux.
+
+use super::RiscVFeatures;
+
+/// `riscv_hwprobe` keys.
+pub mod key {
+    /// Machine vendor ID (`mvendorid` CSR)
+    pub const MVENDORID: i64 = 0;
+    /// Machine architecture ID (`marchid` CSR)
+    pub const MARCHID: i64 = 1;
+    /// Machine implementation ID (`mimpid` CSR)
+    pub const MIMPID: i64 = 2;
+    /// Base behaviour bits
+    pub const BASE_BEHAVIOR: i64 = 3;
+    /// IMA extension bits
+    pub const IMA_EXT_0: i64 = 4;
+    /// Legacy misaligned scalar access performance
+    pub const CPUPERF_0: i64 = 5;
+    /// Zicboz block size in bytes
+    pub const ZICBOZ_BLOCK_SIZE: i64 = 6;
+    /// Highest user-space virtual address
+    pub const HIGHEST_VIRT_ADDRESS: i64 = 7;
+    /// Frequency of the `time` CSR in Hz
+    pub const TIME_CSR_FREQ: i64 = 8;
+    /// Misaligned scalar access performance
+    pub const MISALIGNED_SCALAR_PERF: i64 = 9;
+    /// Misaligned vector access performance
+    pub const MISALIGNED_VECTOR_PERF: i64 = 10;
+    /// T-Head vendor extension bits
+    pub const VENDOR_EXT_THEAD_0: i64 = 11;
+    /// Zicbom block size in bytes
+    pub const ZICBOM_BLOCK_SIZE: i64 = 12;
+}
+
+/// `RISCV_HWPROBE_BASE_BEHAVIOR_IMA`: the base is rv32ima/rv64ima.
+const BASE_BEHAVIOR_IMA: u64 = 1 << 0;
+/// `RISCV_HWPROBE_IMA_FD`: F and D, reported as one bit.
+const IMA_FD: u64 = 1 << 0;
+
+/// Bits of [`key::IMA_EXT_0`] after `IMA_FD`, in kernel order starting at bit 1.
+const IMA_EXT_0_BITS: [RiscVFeatures; 58] = {
+    use RiscVFeatures as F;
+    [
+        F::C,
+        F::V,
+        F::ZBA,
+        F::ZBB,
+        F::ZBS,
+        F::ZICBOZ,
+        F::ZBC,
+        F::ZBKB,
+        F::ZBKC,
+        F::ZBKX,
+        F::ZKND,
+        F::ZKNE,
+        F::ZKNH,
+        F::ZKSED,
+        F::ZKSH,
+        F::ZKT,
+        F::ZVBB,
+        F::ZVBC,
+        F::ZVKB,
+        F::ZVKG,
+        F::ZVKNED,
+        F::ZVKNHA,
+        F::ZVKNHB,
+        F::ZVKSED,
+        F::ZVKSH,
+        F::ZVKT,
+        F::ZFH,
+        F::ZFHMIN,
+        F::ZIHINTNTL,
+        F::ZVFH,
+        F::ZVFHMIN,
+        F::ZFA,
+        F::ZTSO,
+        F::ZACAS,
+        F::ZICOND,
+        F::ZIHINTPAUSE,
+        F::ZVE32X,
+        F::ZVE32F,
+        F::ZVE64X,
+        F::ZVE64F,
+        F::ZVE64D,
+        F::ZIMOP,
+        F::ZCA,
+        F::ZCB,
+        F::ZCD,
+        F::ZCF,
+        F::ZCMOP,
+        F::ZAWRS,
+        F::SUPM,
+        F::ZICNTR,
+        F::ZIHPM,
+        F::ZFBFMIN,
+        F::ZVFBFMIN,
+        F::ZVFBFWMA,
+        F::ZICBOM,
+        F::ZAAMO,
+        F::ZALRSC,
+        F::ZABHA,
+    ]
+};
+
+/// Performance of misaligned memory accesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MisalignedPerf {
+    /// The kernel does not know
+    Unknown,
+    /// Trapped and emulated by the kernel (very slow)
+    Emulated,
+    /// Supported in hardware but slower than aligned accesses
+    Slow,
+    /// Supported in hardware at about the speed of aligned accesses
+    Fast,
+    /// Not supported at all
+    Unsupported,
+}
+
+impl MisalignedPerf {
+    /// Decodes a `RISCV_HWPROBE_MISALIGNED_*` value.
+    pub fn from_value(value: u64) -> MisalignedPerf {
+        match value & 0x7 {
+            1 => MisalignedPerf::Emulated,
+            2 => MisalignedPerf::Slow,
+            3 => MisalignedPerf::Fast,
+            4 => MisalignedPerf::Unsupported,
+            _ => MisalignedPerf::Unknown,
+        }
+    }
+}
+
+/// Decoded `riscv_hwprobe` answers. Fields are `None` when the kernel does not know the key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HwProbe {
+    /// Machine vendor ID
+    pub mvendorid: Option<u64>,
+    /// Machine architecture ID
+    pub marchid: Option<u64>,
+    /// Machine implementation ID
+    pub mimpid: Option<u64>,
+    /// Extensions reported by the base behaviour and IMA extension keys
+    pub features: RiscVFeatures,
+    /// Extensions whose absence from [`HwProbe::features`] is reliable
+    pub covered: RiscVFeatures,
+    /// Raw T-Head vendor extension bits
+    pub vendor_ext_thead: Option<u64>,
+    /// Misaligned scalar access performance
+    pub misaligned_scalar: Option<MisalignedPerf>,
+    /// Misaligned vector access performance
+    pub misaligned_vector: Option<MisalignedPerf>,
+    /// Block size of `cbo.zero`, in bytes
+    pub zicboz_block_size: Option<u64>,
+    /// Block size of `cbo.clean`/`cbo.flush`/`cbo.inval`, in bytes
+    pub zicbom_block_size: Option<u64>,
+    /// Highest user-space virtual address
+    pub highest_virt_address: Option<u64>,
+    /// Frequency of the `time` CSR in Hz
+    pub time_csr_freq: Option<u64>,
+}
+
+impl HwProbe {
+    /// Decodes `(key, value)` pairs as filled in by the syscall.
+    /// Pairs with key `-1` (unknown to the kernel) and unrecognised keys are ignored.
+    pub fn from_pairs(pairs: &[(i64, u64)]) -> HwProbe {
+        let mut hw = HwProbe::default();
+        for &(k, value) in pairs {
+            match k {
+                key::MVENDORID => hw.mvendorid = Some(value),
+                key::MARCHID => hw.marchid = Some(value),
+                key::MIMPID => hw.mimpid = Some(value),
+                key::BASE_BEHAVIOR if value & BASE_BEHAVIOR_IMA != 0 => {
+                    hw.features |= RiscVFeatures::I | RiscVFeatures::M | RiscVFeatures::A;
+                }
+                key::IMA_EXT_0 => {
+                    // Older kernels answer the key without knowing the newer bits, but the
+                    // V bit arrived together with user-mode vector support (6.5), so a
+                    // clear V means the kernel does not enable a V unit
+                    hw.covered |= RiscVFeatures::V;
+                    if value & IMA_FD != 0 {
+                        hw.features |= RiscVFeatures::F | RiscVFeatures::D;
+                    }
+                    for (i, &flag) in IMA_EXT_0_BITS.iter().enumerate() {
+                        if value & (1 << (i + 1)) != 0 {
+                            hw.features |= flag;
+                        }
+                    }
+                }
+                // The newer key wins if both are present
+                key::CPUPERF_0 => {
+                    hw.misaligned_scalar
+                        .get_or_insert(MisalignedPerf::from_value(value));
+                }
+                key::MISALIGNED_SCALAR_PERF => {
+                    hw.misaligned_scalar = Some(MisalignedPerf::from_value(value))
+                }
+                key::MISALIGNED_VECTOR_PERF => {
+                    hw.misaligned_vector = Some(MisalignedPerf::from_value(value))
+                }
+                key::ZICBOZ_BLOCK_SIZE => hw.zicboz_block_size = Some(value),
+                key::ZICBOM_BLOCK_SIZE => hw.zicbom_block_size = Some(value),
+                key::HIGHEST_VIRT_ADDRESS => hw.highest_virt_address = Some(value),
+                key::TIME_CSR_FREQ => hw.time_csr_freq = Some(value),
+                key::VENDOR_EXT_THEAD_0 => hw.vendor_ext_thead = Some(value),
+                _ => (),
+            }
+        }
+        hw
+    }
+
+    /// Combines these answers with the extensions of an ISA string.
+    /// Both sources are merged; hwprobe only overrides the ISA string for `V`,
+    /// which vendor kernels also print for RVV 0.7.1 units.
+    pub fn merge_features(&self, isa_features: RiscVFeatures) -> RiscVFeatures {
+        self.features | isa_features.difference(self.covered)
+    }
+}
+
+/// Every key [`HwProbe::from_pairs`] understands.
+const ALL_KEYS: [i64; 13] = [
+    key::MVENDORID,
+    key::MARCHID,
+    key::MIMPID,
+    key::BASE_BEHAVIOR,
+    key::IMA_EXT_0,
+    key::CPUPERF_0,
+    key::ZICBOZ_BLOCK_SIZE,
+    key::HIGHEST_VIRT_ADDRESS,
+    key::TIME_CSR_FREQ,
+    key::MISALIGNED_SCALAR_PERF,
+    key::MISALIGNED_VECTOR_PERF,
+    key::VENDOR_EXT_THEAD_0,
+    key::ZICBOM_BLOCK_SIZE,
+];
+
+/// Syscall number of `riscv_hwprobe`.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+const SYS_RISCV_HWPROBE: libc::c_long = 258;
+
+/// Issues the syscall for every known key; `cpus` limits the answer to values
+/// common to those CPUs (`None` = all online CPUs).
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+fn syscall(cpus: Option<&libc::cpu_set_t>) -> Option<HwProbe> {
+    #[repr(C)]
+    struct Pair {
+        key: i64,
+        value: u64,
+    }
+    let mut pairs: Vec<Pair> = ALL_KEYS.iter().map(|&key| Pair { key, value: 0 }).collect();
+    let (setsize, set) = match cpus {
+        Some(set) => (core::mem::size_of::<libc::cpu_set_t>(), set as *const _),
+        None => (0, core::ptr::null()),
+    };
+    let ret = unsafe {
+        libc::syscall(
+            SYS_RISCV_HWPROBE,
+            pairs.as_mut_ptr(),
+            pairs.len(),
+            setsize,
+            set,
+            0u32,
+        )
+    };
+    if ret != 0 {
+        return None;
+    }
+    let pairs: Vec<(i64, u64)> = pairs.iter().map(|p| (p.key, p.value)).collect();
+    Some(HwProbe::from_pairs(&pairs))
+}
+
+/// Queries the values common to every online CPU.
+/// Returns `None` on kernels without `riscv_hwprobe` (before 6.4).
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+pub fn probe() -> Option<HwProbe> {
+    syscall(None)
+}
+
+/// Queries the values of one logical CPU.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+/// Returns `None` for IDs that do not fit in a `cpu_set_t`.
+pub fn probe_cpu(cpu_id: usize) -> Option<HwProbe> {
+    if cpu_id >= libc::CPU_SETSIZE as usize {
+        return None;
+    }
+    let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+    unsafe { libc::CPU_SET(cpu_id, &mut set) };
+    syscall(Some(&set))
+}
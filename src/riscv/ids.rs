@@ -0,0 +1,210 @@
+// src/riscv/ids.rs
+//! Machine ID (mvendorid/marchid/mimpid) decoding and the vendor/core database.
+//!
+//! `mvendorid` holds a JEDEC manufacturer ID: bits 31:7 count the `0x7f`
+//! continuation bytes (the bank minus one) and bits 6:0 are the final byte
+//! without its parity bit. A `marchid` with the top bit clear is an entry of the
+//! open-source architecture ID registry; with it set it is vendor-defined.
+
+use super::hwprobe::HwProbe;
+use super::of_node;
+use crate::sysfs::{ProcCpuEntry, Source};
+use core::fmt;
+
+/// The three machine ID CSRs of a hart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MachineIds {
+    /// JEDEC manufacturer ID, `0` for non-commercial implementations
+    pub mvendorid: u64,
+    /// Microarchitecture ID, `0` if not implemented
+    pub marchid: u64,
+    /// Implementation (revision) ID, `0` if not implemented
+    pub mimpid: u64,
+}
+
+impl MachineIds {
+    /// Builds the IDs from the `mvendorid`, `marchid` and `mimpid` fields of a
+    /// `/proc/cpuinfo` block (Linux 6.0+).
+    pub fn from_proc_entry(entry: &ProcCpuEntry) -> Option<MachineIds> {
+        let num = |key: &str| -> Option<u64> {
+            let v = entry.get(key)?;
+            match v.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => v.parse().ok(),
+            }
+        };
+        Some(MachineIds {
+            mvendorid: num("mvendorid")?,
+            marchid: num("marchid").unwrap_or(0),
+            mimpid: num("mimpid").unwrap_or(0),
+        })
+    }
+
+    /// Takes the IDs from `riscv_hwprobe` answers.
+    pub fn from_hwprobe(hw: &HwProbe) -> Option<MachineIds> {
+        Some(MachineIds {
+            mvendorid: hw.mvendorid?,
+            marchid: hw.marchid.unwrap_or(0),
+            mimpid: hw.mimpid.unwrap_or(0),
+        })
+    }
+
+    /// Reads the ID CSRs of the running hart. They are machine-mode only, so this is
+    /// for bare-metal firmware; under an OS use `/proc/cpuinfo` or hwprobe.
+    #[cfg(all(
+        not(target_os = "linux"),
+        any(target_arch = "riscv32", target_arch = "riscv64")
+    ))]
+    pub fn read() -> MachineIds {
+        let (mvendorid, marchid, mimpid): (usize, usize, usize);
+        unsafe {
+            core::arch::asm!(
+                "csrr {0}, mvendorid",
+                "csrr {1}, marchid",
+                "csrr {2}, mimpid",
+                out(reg) mvendorid,
+                out(reg) marchid,
+                out(reg) mimpid,
+                options(nomem, nostack)
+            )
+        };
+        MachineIds {
+            mvendorid: mvendorid as u64,
+            marchid: marchid as u64,
+            mimpid: mimpid as u64,
+        }
+    }
+
+    /// JEDEC bank number (1-based).
+    pub fn jedec_bank(&self) -> u32 {
+        (self.mvendorid >> 7) as u32 + 1
+    }
+
+    /// JEDEC ID within the bank, without the parity bit.
+    pub fn jedec_id(&self) -> u8 {
+        (self.mvendorid & 0x7f) as u8
+    }
+
+    /// Returns `true` if `marchid` is an entry of the open-source registry.
+    /// `marchid` is XLEN bits wide, so the vendor-defined marker is bit `xlen - 1`
+    /// (bit 31 on RV32, bit 63 on RV64 and RV128, whose IDs are truncated here).
+    pub fn is_open_source_arch(&self, xlen: u32) -> bool {
+        let top = xlen.clamp(32, 64) - 1;
+        self.marchid != 0 && (self.marchid >> top) & 1 == 0
+    }
+
+    /// Name of the vendor, e.g. `"SiFive"`.
+    pub fn vendor_name(&self) -> Option<&'static str> {
+        vendor_name(self.mvendorid)
+    }
+
+    /// Name of the core, e.g. `"U74"` or `"XiangShan"`.
+    pub fn core_name(&self) -> Option<&'static str> {
+        core_name(self.mvendorid, self.marchid, self.mimpid)
+    }
+}
+
+impl fmt::Display for MachineIds {
+    /// Formats as `"<vendor> <core>"`, using hex codes for unknown IDs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.vendor_name() {
+            Some(v) => write!(f, "{}", v)?,
+            None => write!(f, "0x{:x}", self.mvendorid)?,
+        }
+        match self.core_name() {
+            Some(c) => write!(f, " {}", c),
+            None => write!(f, " 0x{:x}/0x{:x}", self.marchid, self.mimpid),
+        }
+    }
+}
+
+/// Maps an `mvendorid` to a vendor name.
+pub fn vendor_name(mvendorid: u64) -> Option<&'static str> {
+    Some(match mvendorid {
+        0x029 => "Microchip",
+        0x31e => "Andes",
+        0x489 => "SiFive",
+        0x5b7 => "T-Head",
+        0x710 => "SpacemiT",
+        _ => return None,
+    })
+}
+
+/// Maps the machine IDs to a core name. `mimpid` is only a revision for the
+/// cores listed here, so it is accepted but not matched yet.
+pub fn core_name(mvendorid: u64, marchid: u64, _mimpid: u64) -> Option<&'static str> {
+    Some(match (mvendorid, marchid) {
+        // SiFive; the 7-series ID is shared by U74, S76 and E76, of which only
+        // the U74 runs an OS
+        (0x489, 0x8000_0000_0000_0007) => "U74",
+        (0x489, 0x8000_0000_0000_0008) => "P550",
+        // Andes
+        (0x31e, 0x8000_0000_0000_8a45) => "AX45MP",
+        // SpacemiT
+        (0x710, 0x8000_0000_5800_0001) => "X60",
+        // Open-source architecture ID registry
+        (_, 1) => "Rocket",
+        (_, 2) => "BOOM",
+        (_, 3) => "CVA6",
+        (_, 4) => "CV32E40P",
+        (_, 5) => "Spike",
+        (_, 18) => "SERV",
+        (_, 19) => "NEORV32",
+        (_, 22) => "Ibex",
+        (_, 25) => "XiangShan",
+        _ => return None,
+    })
+}
+
+/// Maps a NUL-separated device-tree `compatible` list to (vendor, core). Used for
+/// cores whose IDs do not tell them apart, e.g. T-Head reports `marchid` 0 on all
+/// of its cores, and for kernels too old to print the IDs.
+pub fn name_from_compatible(compatible: &str) -> Option<(&'static str, &'static str)> {
+    compatible.split('\0').find_map(|c| {
+        Some(match c.trim() {
+            "thead,c906" => ("T-Head", "C906"),
+            "thead,c908" => ("T-Head", "C908"),
+            "thead,c910" => ("T-Head", "C910"),
+            "thead,c920" => ("T-Head", "C920"),
+            "sifive,u54" | "sifive,u54-mc" => ("SiFive", "U54"),
+            "sifive,u74" | "sifive,u74-mc" => ("SiFive", "U74"),
+            "sifive,p550" => ("SiFive", "P550"),
+            "andestech,ax45mp" => ("Andes", "AX45MP"),
+            "spacemit,x60" => ("SpacemiT", "X60"),
+            _ => return None,
+        })
+    })
+}
+
+/// Reads the machine IDs of one logical CPU on the running system.
+pub fn read_cpu(cpu_id: usize) -> Option<MachineIds> {
+    read_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Reads the machine IDs of one logical CPU from `/proc/cpuinfo`.
+pub fn read_cpu_from(src: &Source, cpu_id: usize) -> Option<MachineIds> {
+    src.proc_cpuinfo()
+        .iter()
+        .find(|e| e.processor == Some(cpu_id))
+        .and_then(MachineIds::from_proc_entry)
+}
+
+/// Names the vendor and core of one logical CPU from its IDs, falling back to
+/// the device-tree `compatible` of its node.
+pub fn name_cpu_from(
+    src: &Source,
+    cpu_id: usize,
+    ids: Option<MachineIds>,
+) -> (Option<&'static str>, Option<&'static str>) {
+    let from_ids = ids.map_or((None, None), |i| (i.vendor_name(), i.core_name()));
+    if from_ids.0.is_some() && from_ids.1.is_some() {
+        return from_ids;
+    }
+    let from_dt = src
+        .read(&of_node(cpu_id, "compatible"))
+        .and_then(|c| name_from_compatible(&c));
+    (
+        from_ids.0.or(from_dt.map(|d| d.0)),
+        from_ids.1.or(from_dt.map(|d| d.1)),
+    )
+}
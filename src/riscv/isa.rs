@@ -0,0 +1,180 @@
+// src/riscv/isa.rs
+//! Parser for RISC-V ISA strings such as `rv64imafdcv_zicsr_zba2p0`.
+//!
+//! Follows the ISA naming conventions of the unprivileged specification:
+//! a base (`rv32`/`rv64`/`rv128` with `i`, `e` or `g`), single-letter extensions
+//! with optional versions, then `_`-separated multi-letter `Z*`, `S*` and `X*`
+//! extensions. Parsing is case-insensitive and names are stored in lower case.
+
+use core::fmt;
+
+/// Version attached to an extension, e.g. `2p1` is 2.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IsaVersion {
+    /// Major version
+    pub major: u32,
+    /// Minor version (`0` if only the major version was given)
+    pub minor: u32,
+}
+
+/// One extension of an ISA string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IsaExtension {
+    /// Lower-case name, e.g. `"m"` or `"zicsr"`
+    pub name: String,
+    /// Version, if the string specified one
+    pub version: Option<IsaVersion>,
+}
+
+/// A parsed ISA string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Isa {
+    /// Register width: 32, 64 or 128
+    pub xlen: u32,
+    /// Base integer ISA: `'i'` or `'e'` (`g` expands to `i` plus extensions)
+    pub base: char,
+    /// Every extension in string order, including the base letter and the
+    /// expansion of `g`. Duplicates are dropped.
+    pub extensions: Vec<IsaExtension>,
+}
+
+/// Extensions implied by the `g` base.
+const G_EXPANSION: [&str; 7] = ["i", "m", "a", "f", "d", "zicsr", "zifencei"];
+
+/// Splits a leading `<major>[p<minor>]` version off `s`.
+fn take_version(s: &str) -> (Option<IsaVersion>, &str) {
+    let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+    let Ok(major) = s[..digits].parse() else {
+        return (None, s);
+    };
+    let rest = &s[digits..];
+    if let Some(after_p) = rest.strip_prefix('p') {
+        let minor_digits = after_p.bytes().take_while(u8::is_ascii_digit).count();
+        if let Ok(minor) = after_p[..minor_digits].parse() {
+            return (Some(IsaVersion { major, minor }), &after_p[minor_digits..]);
+        }
+    }
+    (Some(IsaVersion { major, minor: 0 }), rest)
+}
+
+/// Splits a trailing `<major>p<minor>` version off a multi-letter extension.
+/// A bare trailing number is kept as part of the name (e.g. `zve32x`, `sv39`).
+fn split_version(s: &str) -> (&str, Option<IsaVersion>) {
+    let minor_start = s.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if minor_start == s.len() || !s[..minor_start].ends_with('p') {
+        return (s, None);
+    }
+    let head = &s[..minor_start - 1];
+    let major_start = head.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    // The name itself must be left non-empty and end in a letter
+    if major_start == head.len() || major_start < 2 {
+        return (s, None);
+    }
+    match (head[major_start..].parse(), s[minor_start..].parse()) {
+        (Ok(major), Ok(minor)) => (&s[..major_start], Some(IsaVersion { major, minor })),
+        _ => (s, None),
+    }
+}
+
+/// Returns `true` for the prefixes of multi-letter extensions.
+fn is_multi_letter_prefix(c: char) -> bool {
+    matches!(c, 'z' | 's' | 'x')
+}
+
+impl Isa {
+    /// Parses an ISA string. Returns `None` if it does not start with a valid
+    /// base or contains characters outside the naming rules.
+    pub fn parse(s: &str) -> Option<Isa> {
+        let s = s.trim().to_ascii_lowercase();
+        let rest = s.strip_prefix("rv")?;
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let xlen: u32 = rest[..digits].parse().ok()?;
+        if !matches!(xlen, 32 | 64 | 128) {
+            return None;
+        }
+        let rest = &rest[digits..];
+        let base = rest
+            .chars()
+            .next()
+            .filter(|c| matches!(c, 'i' | 'e' | 'g'))?;
+        let (base_version, rest) = take_version(&rest[1..]);
+        let mut isa = Isa {
+            xlen,
+            base: if base == 'e' { 'e' } else { 'i' },
+            extensions: Vec::new(),
+        };
+        if base == 'g' {
+            G_EXPANSION.iter().for_each(|name| isa.push(name, None));
+        } else {
+            isa.push(&base.to_string(), base_version);
+        }
+
+        for segment in rest.split('_') {
+            let mut run = segment;
+            while let Some(c) = run.chars().next() {
+                if is_multi_letter_prefix(c) {
+                    // A multi-letter extension runs to the end of the segment
+                    let (name, version) = split_version(run);
+                    if name.len() < 2 || !name.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                        return None;
+                    }
+                    isa.push(name, version);
+                    break;
+                }
+                if !c.is_ascii_lowercase() {
+                    return None;
+                }
+                let (version, after) = take_version(&run[1..]);
+                if c == 'g' {
+                    G_EXPANSION.iter().for_each(|name| isa.push(name, None));
+                } else {
+                    isa.push(&c.to_string(), version);
+                }
+                run = after;
+            }
+        }
+        Some(isa)
+    }
+
+    /// Appends an extension unless it is already present.
+    fn push(&mut self, name: &str, version: Option<IsaVersion>) {
+        if !self.has(name) {
+            self.extensions.push(IsaExtension {
+                name: name.to_string(),
+                version,
+            });
+        }
+    }
+
+    /// Returns the extension named `name` (case-insensitive).
+    pub fn extension(&self, name: &str) -> Option<&IsaExtension> {
+        self.extensions
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns `true` if the ISA includes the extension named `name` (case-insensitive).
+    pub fn has(&self, name: &str) -> bool {
+        self.extension(name).is_some()
+    }
+}
+
+impl fmt::Display for Isa {
+    /// Formats as a canonical ISA string: single letters first, then `_`-separated
+    /// multi-letter extensions, each with its version if known.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rv{}", self.xlen)?;
+        let (single, multi): (Vec<_>, Vec<_>) =
+            self.extensions.iter().partition(|e| e.name.len() == 1);
+        for (i, ext) in single.iter().chain(&multi).enumerate() {
+            if ext.name.len() > 1 && i > 0 {
+                f.write_str("_")?;
+            }
+            f.write_str(&ext.name)?;
+            if let Some(v) = ext.version {
+                write!(f, "{}p{}", v.major, v.minor)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+// src/riscv/profile.rs
+//! Compliance with the ratified RISC-V user-mode profiles for RV64.
+//!
+//! Profiles are the RISC-V counterpart of the x86-64 levels: RVA* for application
+//! processors (what distributions build for) and RVB23 for customised, often
+//! embedded, application processors. Only extensions with a feature flag are
+//! checked, and kernels list the Ziccif/Ziccrse/... memory guarantees only since 6.11,
+//! so older kernels under-report by those.
+
+use super::RiscVFeatures;
+use core::fmt;
+
+/// A ratified 64-bit user-mode profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiscVProfile {
+    /// RVI20U64: the unprivileged base only
+    Rvi20U64,
+    /// RVA20U64
+    Rva20U64,
+    /// RVA22U64
+    Rva22U64,
+    /// RVA23U64
+    Rva23U64,
+    /// RVB23U64
+    Rvb23U64,
+}
+
+/// The cumulative RVI/RVA line, in order.
+const RVA_LINE: [RiscVProfile; 4] = [
+    RiscVProfile::Rvi20U64,
+    RiscVProfile::Rva20U64,
+    RiscVProfile::Rva22U64,
+    RiscVProfile::Rva23U64,
+];
+
+impl RiscVProfile {
+    /// Returns every mandatory extension of the profile.
+    pub fn required(self) -> RiscVFeatures {
+        use RiscVFeatures as F;
+        let rva20 = F::I
+            | F::M
+            | F::A
+            | F::F
+            | F::D
+            | F::C
+            | F::ZICSR
+            | F::ZICNTR
+            | F::ZICCIF
+            | F::ZICCRSE
+            | F::ZICCAMOA
+            | F::ZICCLSM
+            | F::ZA128RS;
+        let rva22 = (rva20 - F::ZA128RS)
+            | F::ZIHPM
+            | F::ZA64RS
+            | F::ZIHINTPAUSE
+            | F::ZBA
+            | F::ZBB
+            | F::ZBS
+            | F::ZIC64B
+            | F::ZICBOM
+            | F::ZICBOP
+            | F::ZICBOZ
+            | F::ZFHMIN
+            | F::ZKT;
+        let rvb23 = (rva22 - F::ZFHMIN)
+            | F::ZIHINTNTL
+            | F::ZICOND
+            | F::ZIMOP
+            | F::ZCMOP
+            | F::ZCB
+            | F::ZFA
+            | F::ZAWRS;
+        match self {
+            RiscVProfile::Rvi20U64 => F::I,
+            RiscVProfile::Rva20U64 => rva20,
+            RiscVProfile::Rva22U64 => rva22,
+            RiscVProfile::Rva23U64 => {
+                rvb23 | F::ZFHMIN | F::V | F::ZVFHMIN | F::ZVBB | F::ZVKT | F::SUPM
+            }
+            RiscVProfile::Rvb23U64 => rvb23,
+        }
+    }
+
+    /// Returns the required extensions missing from `features`.
+    pub fn missing(self, features: RiscVFeatures) -> RiscVFeatures {
+        self.required() - implied(features)
+    }
+
+    /// Returns `true` if `features` contains every required extension.
+    pub fn is_satisfied_by(self, features: RiscVFeatures) -> bool {
+        self.missing(features).is_empty()
+    }
+}
+
+/// Adds the extensions implied by others, so `d` counts as `f` and a 64-byte
+/// reservation set guarantee counts as the 128-byte one.
+fn implied(features: RiscVFeatures) -> RiscVFeatures {
+    use RiscVFeatures as F;
+    let mut f = features;
+    if f.contains(F::D) {
+        f |= F::F;
+    }
+    if f.intersects(F::F | F::ZICNTR | F::ZIHPM) {
+        f |= F::ZICSR;
+    }
+    if f.contains(F::ZA64RS) {
+        f |= F::ZA128RS;
+    }
+    f
+}
+
+impl fmt::Display for RiscVProfile {
+    /// Formats as the profile name, e.g. `"RVA22U64"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RiscVProfile::Rvi20U64 => "RVI20U64",
+            RiscVProfile::Rva20U64 => "RVA20U64",
+            RiscVProfile::Rva22U64 => "RVA22U64",
+            RiscVProfile::Rva23U64 => "RVA23U64",
+            RiscVProfile::Rvb23U64 => "RVB23U64",
+        })
+    }
+}
+
+/// Result of [`infer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileReport {
+    /// Highest RVI20/RVA profile fully satisfied, `None` if not even RVI20U64
+    pub profile: Option<RiscVProfile>,
+    /// Whether RVB23U64 is satisfied
+    pub rvb23: bool,
+    /// The next RVA profile and RVB23U64 if unsatisfied, each with the extensions blocking it
+    pub next: Vec<(RiscVProfile, RiscVFeatures)>,
+}
+
+/// Evaluates an RV64 feature set against the ratified profiles.
+pub fn infer(features: RiscVFeatures) -> ProfileReport {
+    // The RVA line is cumulative, so stop at the first profile that fails
+    let profile = RVA_LINE
+        .iter()
+        .take_while(|p| p.is_satisfied_by(features))
+        .last()
+        .copied();
+    let i = profile.map_or(0, |p| RVA_LINE.iter().position(|&l| l == p).unwrap() + 1);
+    let rvb23 = RiscVProfile::Rvb23U64.is_satisfied_by(features);
+
+    let next = RVA_LINE
+        .get(i)
+        .into_iter()
+        .chain((!rvb23).then_some(&RiscVProfile::Rvb23U64))
+        .map(|&p| (p, p.missing(features)))
+        .collect();
+
+    ProfileReport {
+        profile,
+        rvb23,
+        next,
+    }
+}
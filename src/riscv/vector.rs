@@ -0,0 +1,123 @@
+// src/riscv/vector.rs
+//! Vector unit detection: ratified RVV 1.0 versus T-Head's pre-ratification
+//! RVV 0.7.1 (`xtheadvector`), and the VLEN/ELEN of the unit.
+//!
+//! The two are binary-incompatible, but vendor kernels for C906/C910/C920 boards
+//! report the 0.7.1 unit as plain `v`, so the ISA string alone cannot tell them apart.
+
+use super::hwprobe::HwProbe;
+use super::isa::Isa;
+use super::{of_node, RiscVFeatures};
+use crate::sysfs::Source;
+
+/// `RISCV_HWPROBE_VENDOR_EXT_XTHEADVECTOR` in [`HwProbe::vendor_ext_thead`].
+const HWPROBE_XTHEADVECTOR: u64 = 1 << 0;
+
+/// Device-tree `compatible` strings of cores whose vector unit is RVV 0.7.1.
+const THEAD_RVV_0_7_CORES: [&str; 3] = ["thead,c906", "thead,c910", "thead,c920"];
+
+/// Which vector specification the unit implements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorKind {
+    /// Ratified RVV 1.0 (`V` or one of the `Zve*` subsets)
+    Rvv1_0,
+    /// T-Head RVV 0.7.1 (`xtheadvector`)
+    XTheadVector,
+}
+
+/// Vector unit of a hart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VectorInfo {
+    /// Vector specification
+    pub kind: VectorKind,
+    /// Vector register length in bits, if known
+    pub vlen: Option<u32>,
+    /// Largest supported element width in bits
+    pub elen: u32,
+}
+
+/// Flags that stand for an RVV 1.0 unit.
+pub fn rvv1_0_features() -> RiscVFeatures {
+    use RiscVFeatures as F;
+    F::V | F::ZVE32X | F::ZVE32F | F::ZVE64X | F::ZVE64F | F::ZVE64D
+}
+
+/// Returns `true` if a NUL-separated device-tree `compatible` list names a core
+/// with an RVV 0.7.1 unit.
+pub fn is_thead_rvv_0_7_core(compatible: &str) -> bool {
+    compatible
+        .split('\0')
+        .any(|c| THEAD_RVV_0_7_CORES.contains(&c.trim()))
+}
+
+/// Reads the `thead,vlenb` device-tree property (big-endian cell, in bytes).
+pub fn read_dt_vlenb_from(src: &Source, cpu_id: usize) -> Option<u32> {
+    let bytes = src.read_bytes(&of_node(cpu_id, "thead,vlenb"))?;
+    let cell: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+    Some(u32::from_be_bytes(cell))
+}
+
+/// Classifies the vector unit of hart `cpu_id` from what the OS reports.
+/// Returns `None` if the hart has no vector unit.
+///
+/// `features` may still contain `V` for a 0.7.1 unit; the caller moves it to
+/// [`RiscVFeatures::XTHEADVECTOR`] when the result says so.
+pub fn detect_from(
+    src: &Source,
+    cpu_id: usize,
+    features: RiscVFeatures,
+    isa: Option<&Isa>,
+    hwprobe: Option<&HwProbe>,
+) -> Option<VectorInfo> {
+    let hwprobe_v = hwprobe.map_or(RiscVFeatures::empty(), |hw| hw.features) & rvv1_0_features();
+    let vendor_bit = hwprobe
+        .and_then(|hw| hw.vendor_ext_thead)
+        .is_some_and(|bits| bits & HWPROBE_XTHEADVECTOR != 0);
+    let listed_v0_7 = isa
+        .and_then(|isa| isa.extension("v"))
+        .and_then(|v| v.version)
+        .is_some_and(|v| (v.major, v.minor) == (0, 7));
+    let compatible = src.read(&of_node(cpu_id, "compatible")).unwrap_or_default();
+
+    let kind = if vendor_bit || listed_v0_7 || isa.is_some_and(|isa| isa.has("xtheadvector")) {
+        VectorKind::XTheadVector
+    } else if !hwprobe_v.is_empty() {
+        // Upstream kernels only report V through hwprobe for RVV 1.0 units
+        VectorKind::Rvv1_0
+    } else if features.contains(RiscVFeatures::XTHEADVECTOR)
+        || (features.intersects(rvv1_0_features()) && is_thead_rvv_0_7_core(&compatible))
+    {
+        VectorKind::XTheadVector
+    } else if features.intersects(rvv1_0_features()) {
+        VectorKind::Rvv1_0
+    } else {
+        return None;
+    };
+
+    let vector = features | hwprobe_v;
+    // Every Zve64* subset implies Zve64x, and V implies Zve64d
+    let elen64 =
+        RiscVFeatures::V | RiscVFeatures::ZVE64X | RiscVFeatures::ZVE64F | RiscVFeatures::ZVE64D;
+    let (vlen, elen) = match kind {
+        VectorKind::XTheadVector => (read_dt_vlenb_from(src, cpu_id).map(|b| b * 8), 64),
+        VectorKind::Rvv1_0 if vector.intersects(elen64) => (None, 64),
+        VectorKind::Rvv1_0 => (None, 32),
+    };
+    Some(VectorInfo { kind, vlen, elen })
+}
+
+/// Reads the `vlenb` CSR (VLEN in bytes) of the running hart.
+///
+/// Only call this when hwprobe or `AT_HWCAP` reports a usable RVV 1.0 unit: the
+/// CSR does not exist on RVV 0.7.1 or scalar-only cores, and reading it traps
+/// while the kernel has not enabled V for user mode.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "riscv32", target_arch = "riscv64")
+))]
+pub fn read_vlenb() -> u32 {
+    let vlenb: usize;
+    // 0xc22 is vlenb; the number assembles without enabling V for the whole crate
+    unsafe { core::arch::asm!("csrr {0}, 0xc22", out(reg) vlenb, options(nomem, nostack)) };
+    vlenb as u32
+}
@@ -0,0 +1,221 @@
+// src/sysfs.rs
+//! Helpers for reading CPU information exposed by the Linux kernel in
+//! sysfs and procfs.
+//!
+//! On other operating systems the files simply do not exist, so every reader
+//! returns `None` or an empty list instead of failing.
+//!
+//! All readers go through a [`Source`], which is either the live filesystem,
+//! a captured tree under another root directory, or an in-memory map of files.
+//! Each module's `read()`-style function uses [`Source::live`]; the matching
+//! `*_from` function takes an explicit source.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Root of the per-CPU sysfs hierarchy.
+pub const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Path of the kernel's list of online logical CPUs.
+pub const CPU_ONLINE: &str = "/sys/devices/system/cpu/online";
+
+/// Parses a kernel CPU list such as `"0-3,8,10-11"` into sorted CPU IDs.
+///
+/// This is the format used by `online`, `cpulist`, `shared_cpu_list` and
+/// friends. Malformed entries are skipped and duplicates are removed.
+pub fn parse_cpu_list(s: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in s.trim().split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        match part.split_once('-') {
+            Some((lo, hi)) => {
+                if let (Ok(lo), Ok(hi)) = (lo.trim().parse::<usize>(), hi.trim().parse::<usize>()) {
+                    cpus.extend(lo..=hi);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.parse::<usize>() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Where procfs and sysfs files are read from.
+///
+/// Paths passed to the methods are always the absolute paths used on a live
+/// system, such as `/proc/cpuinfo`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// Files are read from the real filesystem, below this root directory
+    Root(PathBuf),
+    /// Files are looked up in a map from absolute path to contents
+    Memory(BTreeMap<String, String>),
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::live()
+    }
+}
+
+impl Source {
+    /// The running system's `/proc` and `/sys`.
+    pub fn live() -> Source {
+        Source::Root(PathBuf::from("/"))
+    }
+
+    /// A captured tree, e.g. a directory containing `proc/cpuinfo` and
+    /// `sys/devices/system/cpu/...` copied from another machine.
+    pub fn root(root: impl Into<PathBuf>) -> Source {
+        Source::Root(root.into())
+    }
+
+    /// An in-memory set of files, keyed by absolute path.
+    pub fn memory<I, K, V>(files: I) -> Source
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Source::Memory(
+            files
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    /// Reads a file, or returns `None` if it does not exist or cannot be read.
+    pub fn read(&self, path: &str) -> Option<String> {
+        match self {
+            Source::Root(root) => {
+                std::fs::read_to_string(root.join(path.trim_start_matches('/'))).ok()
+            }
+            Source::Memory(files) => files.get(path).cloned(),
+        }
+    }
+
+    /// Reads a binary file such as a device-tree property.
+    pub fn read_bytes(&self, path: &str) -> Option<Vec<u8>> {
+        match self {
+            Source::Root(root) => std::fs::read(root.join(path.trim_start_matches('/'))).ok(),
+            Source::Memory(files) => files.get(path).map(|s| s.as_bytes().to_vec()),
+        }
+    }
+
+    /// Reads a file and returns its contents with surrounding whitespace removed.
+    pub fn read_trimmed(&self, path: &str) -> Option<String> {
+        self.read(path).map(|s| s.trim().to_string())
+    }
+
+    /// Returns the sorted names of the entries in a directory (empty if it does not exist).
+    pub fn list_dir(&self, path: &str) -> Vec<String> {
+        let mut names: Vec<String> = match self {
+            Source::Root(root) => std::fs::read_dir(root.join(path.trim_start_matches('/')))
+                .map(|entries| {
+                    entries
+                        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Source::Memory(files) => {
+                let prefix = format!("{}/", path.trim_end_matches('/'));
+                files
+                    .keys()
+                    .filter_map(|k| k.strip_prefix(&prefix))
+                    .filter_map(|rest| rest.split('/').next())
+                    .map(str::to_string)
+                    .collect()
+            }
+        };
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Returns the IDs of all online logical CPUs, or `None` if the kernel does
+    /// not expose [`CPU_ONLINE`].
+    pub fn online_cpus(&self) -> Option<Vec<usize>> {
+        self.read_trimmed(CPU_ONLINE).map(|s| parse_cpu_list(&s))
+    }
+
+    /// Returns the CPUs the process may run on, from `Cpus_allowed_list` in
+    /// [`PROC_SELF_STATUS`]. This is the process-wide mask, unlike
+    /// `sched_getaffinity(0)` which reports only the calling thread's, and it has no
+    /// `CPU_SETSIZE` limit.
+    pub fn allowed_cpus(&self) -> Option<Vec<usize>> {
+        self.read(PROC_SELF_STATUS)?
+            .lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+            .map(parse_cpu_list)
+    }
+
+    /// Reads and parses `/proc/cpuinfo`.
+    pub fn proc_cpuinfo(&self) -> Vec<ProcCpuEntry> {
+        self.read(PROC_CPUINFO)
+            .map(|s| parse_proc_cpuinfo(&s))
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the IDs of all online logical CPUs on the running system.
+pub fn online_cpus() -> Option<Vec<usize>> {
+    Source::live().online_cpus()
+}
+
+/// Path of the calling process's status file.
+pub const PROC_SELF_STATUS: &str = "/proc/self/status";
+
+/// Path of the kernel's per-processor description file.
+pub const PROC_CPUINFO: &str = "/proc/cpuinfo";
+
+/// One processor block of `/proc/cpuinfo`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProcCpuEntry {
+    /// Value of the `processor` field, if it is numeric
+    pub processor: Option<usize>,
+    /// Every `key : value` pair in the block, in file order
+    pub fields: Vec<(String, String)>,
+}
+
+impl ProcCpuEntry {
+    /// Returns the value of the first field named `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Splits `/proc/cpuinfo` contents into blocks separated by blank lines.
+/// Keys and values are trimmed, so `"model name\t: Foo"` becomes `("model name", "Foo")`.
+pub fn parse_proc_cpuinfo(s: &str) -> Vec<ProcCpuEntry> {
+    let mut entries = Vec::new();
+    let mut cur = ProcCpuEntry::default();
+    for line in s.lines() {
+        let Some((key, val)) = line.split_once(':') else {
+            if line.trim().is_empty() && !cur.fields.is_empty() {
+                entries.push(std::mem::take(&mut cur));
+            }
+            continue;
+        };
+        let (key, val) = (key.trim(), val.trim());
+        if key == "processor" {
+            cur.processor = val.parse().ok();
+        }
+        cur.fields.push((key.to_string(), val.to_string()));
+    }
+    if !cur.fields.is_empty() {
+        entries.push(cur);
+    }
+    entries
+}
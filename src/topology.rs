@@ -0,0 +1,225 @@
+// src/topology.rs
+//! Architecture-independent CPU topology read from Linux sysfs.
+//!
+//! Every online CPU's `/sys/devices/system/cpu/cpuN/topology/` directory is
+//! parsed and the results are grouped into a tree of packages, dies, clusters,
+//! cores and hardware threads.
+
+use std::collections::BTreeMap;
+
+use crate::sysfs::{self, Source};
+
+/// Topology attributes of a single logical CPU, as reported by the kernel.
+/// IDs are `-1` when the kernel does not know them (common for `cluster_id`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CpuTopology {
+    /// OS logical CPU ID
+    pub cpu_id: usize,
+    /// Physical package (socket) ID
+    pub package_id: i32,
+    /// Die ID within the package
+    pub die_id: i32,
+    /// Cluster ID within the die (e.g. an ARM DynamIQ cluster)
+    pub cluster_id: i32,
+    /// Core ID within the package
+    pub core_id: i32,
+    /// CPUs sharing this core (SMT siblings, including this CPU)
+    pub thread_siblings: Vec<usize>,
+    /// CPUs sharing this cluster
+    pub cluster_cpus: Vec<usize>,
+    /// CPUs sharing this die
+    pub die_cpus: Vec<usize>,
+    /// CPUs sharing this package
+    pub package_cpus: Vec<usize>,
+}
+
+/// A physical core and the logical CPUs (hardware threads) running on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Core {
+    /// Core ID as reported by the kernel
+    pub id: i32,
+    /// OS CPU IDs of the hardware threads on this core
+    pub threads: Vec<usize>,
+}
+
+/// A cluster of cores sharing resources such as an L2 cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cluster {
+    /// Cluster ID as reported by the kernel
+    pub id: i32,
+    /// Cores in this cluster
+    pub cores: Vec<Core>,
+}
+
+/// A die within a package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Die {
+    /// Die ID as reported by the kernel
+    pub id: i32,
+    /// Clusters on this die
+    pub clusters: Vec<Cluster>,
+}
+
+/// A physical package (socket).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Package {
+    /// Package ID as reported by the kernel
+    pub id: i32,
+    /// Dies in this package
+    pub dies: Vec<Die>,
+}
+
+/// System topology tree plus the flat per-CPU records it was built from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Topology {
+    /// Packages, sorted by ID
+    pub packages: Vec<Package>,
+    /// Per-CPU records, sorted by CPU ID
+    pub cpus: Vec<CpuTopology>,
+}
+
+impl Topology {
+    /// Builds the tree from per-CPU records.
+    pub fn from_cpus(mut cpus: Vec<CpuTopology>) -> Topology {
+        cpus.sort_by_key(|c| c.cpu_id);
+        type Tree = BTreeMap<i32, BTreeMap<i32, BTreeMap<i32, BTreeMap<i32, Vec<usize>>>>>;
+        let mut tree: Tree = BTreeMap::new();
+        for c in &cpus {
+            tree.entry(c.package_id)
+                .or_default()
+                .entry(c.die_id)
+                .or_default()
+                .entry(c.cluster_id)
+                .or_default()
+                .entry(c.core_id)
+                .or_default()
+                .push(c.cpu_id);
+        }
+        let packages = tree
+            .into_iter()
+            .map(|(id, dies)| Package {
+                id,
+                dies: dies
+                    .into_iter()
+                    .map(|(id, clusters)| Die {
+                        id,
+                        clusters: clusters
+                            .into_iter()
+                            .map(|(id, cores)| Cluster {
+                                id,
+                                cores: cores
+                                    .into_iter()
+                                    .map(|(id, threads)| Core { id, threads })
+                                    .collect(),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Topology { packages, cpus }
+    }
+
+    /// Returns an iterator over every physical core in the system.
+    pub fn cores(&self) -> impl Iterator<Item = &Core> {
+        self.packages
+            .iter()
+            .flat_map(|p| &p.dies)
+            .flat_map(|d| &d.clusters)
+            .flat_map(|c| &c.cores)
+    }
+
+    /// Number of physical packages (sockets).
+    pub fn package_count(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Number of physical cores across all packages.
+    pub fn core_count(&self) -> usize {
+        self.cores().count()
+    }
+
+    /// Number of logical CPUs.
+    pub fn thread_count(&self) -> usize {
+        self.cpus.len()
+    }
+
+    /// Largest number of hardware threads on any core (1 without SMT).
+    pub fn threads_per_core(&self) -> usize {
+        self.cores().map(|c| c.threads.len()).max().unwrap_or(1)
+    }
+
+    /// Returns the record for a logical CPU.
+    pub fn cpu(&self, cpu_id: usize) -> Option<&CpuTopology> {
+        self.cpus
+            .binary_search_by_key(&cpu_id, |c| c.cpu_id)
+            .ok()
+            .map(|i| &self.cpus[i])
+    }
+}
+
+/// Reads a signed ID file, returning `-1` if it is missing or malformed.
+fn read_id(src: &Source, path: &str) -> i32 {
+    src.read_trimmed(path)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(-1)
+}
+
+/// Reads the first CPU list file that exists out of `names`.
+fn read_list(src: &Source, dir: &str, names: &[&str]) -> Vec<usize> {
+    names
+        .iter()
+        .find_map(|name| src.read_trimmed(&format!("{}/{}", dir, name)))
+        .map(|s| sysfs::parse_cpu_list(&s))
+        .unwrap_or_default()
+}
+
+/// Reads the topology attributes of one logical CPU on the running system.
+pub fn read_cpu(cpu_id: usize) -> Option<CpuTopology> {
+    read_cpu_from(&Source::live(), cpu_id)
+}
+
+/// Reads the topology attributes of one logical CPU.
+/// Returns `None` if the CPU has no `topology` directory (offline or not Linux).
+pub fn read_cpu_from(src: &Source, cpu_id: usize) -> Option<CpuTopology> {
+    let dir = format!("{}/cpu{}/topology", sysfs::CPU_ROOT, cpu_id);
+    let core_id = src
+        .read_trimmed(&format!("{}/core_id", dir))?
+        .parse()
+        .ok()?;
+    let thread_siblings = read_list(src, &dir, &["core_cpus_list", "thread_siblings_list"]);
+    Some(CpuTopology {
+        cpu_id,
+        package_id: read_id(src, &format!("{}/physical_package_id", dir)),
+        die_id: read_id(src, &format!("{}/die_id", dir)),
+        cluster_id: read_id(src, &format!("{}/cluster_id", dir)),
+        core_id,
+        thread_siblings: if thread_siblings.is_empty() {
+            vec![cpu_id]
+        } else {
+            thread_siblings
+        },
+        cluster_cpus: read_list(src, &dir, &["cluster_cpus_list"]),
+        die_cpus: read_list(src, &dir, &["die_cpus_list"]),
+        package_cpus: read_list(src, &dir, &["package_cpus_list", "core_siblings_list"]),
+    })
+}
+
+/// Reads the topology of every online CPU on the running system.
+pub fn read() -> Option<Topology> {
+    read_from(&Source::live())
+}
+
+/// Reads the topology of every online CPU.
+/// Returns `None` if sysfs topology information is unavailable.
+pub fn read_from(src: &Source) -> Option<Topology> {
+    let cpus: Vec<CpuTopology> = src
+        .online_cpus()?
+        .into_iter()
+        .filter_map(|cpu| read_cpu_from(src, cpu))
+        .collect();
+    if cpus.is_empty() {
+        return None;
+    }
+    Some(Topology::from_cpus(cpus))
+}
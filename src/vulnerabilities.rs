@@ -0,0 +1,162 @@
+// src/vulnerabilities.rs
+//! Kernel-reported CPU vulnerability status from
+//! `/sys/devices/system/cpu/vulnerabilities/`.
+
+use crate::sysfs::Source;
+
+/// Directory holding one file per known CPU vulnerability.
+pub const VULNERABILITIES_DIR: &str = "/sys/devices/system/cpu/vulnerabilities";
+
+/// Status of a single vulnerability as reported by the kernel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VulnStatus {
+    /// The CPU is not affected
+    NotAffected,
+    /// The CPU is affected and not (fully) mitigated, with the kernel's detail if any
+    Vulnerable(Option<String>),
+    /// The CPU is affected and mitigated, with the kernel's description of the mitigation
+    Mitigated(String),
+    /// Any other report, such as `"Unknown: Dependent on hypervisor status"`
+    Unknown(String),
+}
+
+impl VulnStatus {
+    /// Parses the contents of a vulnerability file.
+    /// The `"KVM: "` prefix of `itlb_multihit` is dropped, and the older
+    /// `"Processor vulnerable"` wording counts as vulnerable.
+    pub fn parse(s: &str) -> VulnStatus {
+        let s = s.trim();
+        let s = s.strip_prefix("KVM:").map_or(s, str::trim_start);
+        if s == "Not affected" {
+            VulnStatus::NotAffected
+        } else if let Some(rest) = s.strip_prefix("Mitigation:") {
+            VulnStatus::Mitigated(rest.trim().to_string())
+        } else if let Some(rest) = s
+            .strip_prefix("Vulnerable")
+            .or_else(|| s.strip_prefix("Processor vulnerable"))
+        {
+            let detail = rest.trim_start_matches([':', ';', ',']).trim();
+            VulnStatus::Vulnerable((!detail.is_empty()).then(|| detail.to_string()))
+        } else {
+            VulnStatus::Unknown(s.to_string())
+        }
+    }
+
+    /// Returns `true` for [`VulnStatus::Vulnerable`].
+    pub fn is_vulnerable(&self) -> bool {
+        matches!(self, VulnStatus::Vulnerable(_))
+    }
+}
+
+/// Kernel vulnerability report. Fields are `None` when the running kernel
+/// predates the corresponding sysfs file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vulnerabilities {
+    /// Spectre variant 1 (bounds check bypass)
+    pub spectre_v1: Option<VulnStatus>,
+    /// Spectre variant 2 (branch target injection)
+    pub spectre_v2: Option<VulnStatus>,
+    /// Meltdown (rogue data cache load)
+    pub meltdown: Option<VulnStatus>,
+    /// Speculative Store Bypass (Spectre v4)
+    pub spec_store_bypass: Option<VulnStatus>,
+    /// L1 Terminal Fault
+    pub l1tf: Option<VulnStatus>,
+    /// Microarchitectural Data Sampling
+    pub mds: Option<VulnStatus>,
+    /// TSX Asynchronous Abort
+    pub tsx_async_abort: Option<VulnStatus>,
+    /// iTLB multihit
+    pub itlb_multihit: Option<VulnStatus>,
+    /// Special Register Buffer Data Sampling
+    pub srbds: Option<VulnStatus>,
+    /// Processor MMIO stale data
+    pub mmio_stale_data: Option<VulnStatus>,
+    /// Retbleed
+    pub retbleed: Option<VulnStatus>,
+    /// Speculative Return Stack Overflow (AMD Inception)
+    pub spec_rstack_overflow: Option<VulnStatus>,
+    /// Gather Data Sampling (Downfall)
+    pub gather_data_sampling: Option<VulnStatus>,
+    /// Register File Data Sampling
+    pub reg_file_data_sampling: Option<VulnStatus>,
+    /// Every file in the directory, including those without a dedicated field,
+    /// sorted by name
+    pub all: Vec<(String, VulnStatus)>,
+}
+
+impl Vulnerabilities {
+    /// Builds a report from `(file name, contents)` pairs.
+    pub fn from_entries<I, K, V>(entries: I) -> Vulnerabilities
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: AsRef<str>,
+    {
+        let mut v = Vulnerabilities::default();
+        for (name, contents) in entries {
+            let name = name.into();
+            let status = VulnStatus::parse(contents.as_ref());
+            let slot = match name.as_str() {
+                "spectre_v1" => &mut v.spectre_v1,
+                "spectre_v2" => &mut v.spectre_v2,
+                "meltdown" => &mut v.meltdown,
+                "spec_store_bypass" => &mut v.spec_store_bypass,
+                "l1tf" => &mut v.l1tf,
+                "mds" => &mut v.mds,
+                "tsx_async_abort" => &mut v.tsx_async_abort,
+                "itlb_multihit" => &mut v.itlb_multihit,
+                "srbds" => &mut v.srbds,
+                "mmio_stale_data" => &mut v.mmio_stale_data,
+                "retbleed" => &mut v.retbleed,
+                "spec_rstack_overflow" => &mut v.spec_rstack_overflow,
+                "gather_data_sampling" => &mut v.gather_data_sampling,
+                "reg_file_data_sampling" => &mut v.reg_file_data_sampling,
+                _ => {
+                    v.all.push((name, status));
+                    continue;
+                }
+            };
+            *slot = Some(status.clone());
+            v.all.push((name, status));
+        }
+        v.all.sort_by(|a, b| a.0.cmp(&b.0));
+        v
+    }
+
+    /// Returns the status of a vulnerability by its sysfs file name.
+    pub fn get(&self, name: &str) -> Option<&VulnStatus> {
+        self.all.iter().find(|(n, _)| n == name).map(|(_, s)| s)
+    }
+
+    /// Returns the names of all vulnerabilities the kernel reports as unmitigated.
+    pub fn vulnerable(&self) -> Vec<&str> {
+        self.all
+            .iter()
+            .filter(|(_, s)| s.is_vulnerable())
+            .map(|(n, _)| n.as_str())
+            .collect()
+    }
+}
+
+/// Reads the running kernel's vulnerability report.
+pub fn read() -> Option<Vulnerabilities> {
+    read_from(&Source::live())
+}
+
+/// Reads the kernel's vulnerability report.
+/// Returns `None` if the directory does not exist (old kernel or not Linux).
+pub fn read_from(src: &Source) -> Option<Vulnerabilities> {
+    let names = src.list_dir(VULNERABILITIES_DIR);
+    if names.is_empty() {
+        return None;
+    }
+    let pairs: Vec<(String, String)> = names
+        .into_iter()
+        .filter_map(|name| {
+            let contents = src.read_trimmed(&format!("{}/{}", VULNERABILITIES_DIR, name))?;
+            Some((name, contents))
+        })
+        .collect();
+    Some(Vulnerabilities::from_entries(pairs))
+}
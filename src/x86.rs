@@ -1,11 +1,28 @@
 // src/x86.rs
 #![allow(dead_code)]
+use crate::cache::{self, CacheInfo, CacheType};
+use crate::cpufreq::{self, CpuFreq};
+use crate::numa;
+use crate::sysfs::{self, ProcCpuEntry, Source};
 use bitflags::bitflags;
 use core::fmt;
 #[cfg(target_os = "linux")]
-use libc::{cpu_set_t, pthread_self, pthread_setaffinity_np, sched_getcpu, CPU_SET, CPU_ZERO};
+use libc::{
+    cpu_set_t, getpid, pthread_self, pthread_setaffinity_np, sched_getaffinity, sched_getcpu,
+    CPU_ISSET, CPU_SETSIZE,
+};
 use once_cell::sync::Lazy;
-use std::{sync::Arc, thread};
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_os = "linux")]
+use std::time::Duration;
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, PoisonError, RwLock,
+    },
+    thread,
+};
 #[cfg(windows)]
 use winapi::um::processthreadsapi::GetCurrentProcessorNumber;
 
@@ -137,19 +154,46 @@ macro_rules! cpuid_flags {
 }
 
 /// Enum representing the type of CPU core.
-/// Used for hybrid architectures (e.g., Intel Alder Lake).
-#[derive(Clone, Copy, Debug)]
+/// Used for hybrid architectures (e.g., Intel Alder Lake, Meteor Lake).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CoreType {
     /// High-performance core (P-core)
     Performance,
     /// High-efficiency core (E-core)
     Efficiency,
+    /// Low-power efficiency core on the SoC tile (LP E-core, Meteor Lake and later).
+    /// Reported as an Atom core by CPUID but without access to the L3 cache.
+    LowPowerEfficiency,
+    /// Core type value from CPUID(0x1A).EAX[31:24] that is not recognised
+    Unknown(u8),
+}
+
+impl CoreType {
+    /// Decodes the core type byte from CPUID(0x1A).EAX[31:24].
+    /// `has_l3` separates LP E-cores from regular E-cores, since both report as Atom.
+    pub fn from_cpuid(core_type: u8, has_l3: bool) -> CoreType {
+        match core_type {
+            0x20 if has_l3 => CoreType::Efficiency,
+            0x20 => CoreType::LowPowerEfficiency,
+            0x40 => CoreType::Performance,
+            other => CoreType::Unknown(other),
+        }
+    }
 }
 
 /// Stores information about a single logical x86 CPU.
 /// Includes vendor, brand string, feature flags, core/thread counts, and hybrid core type.
 #[derive(Clone, Debug)]
 pub struct X86CpuInfo {
+    /// OS logical CPU ID this info was probed on
+    pub cpu_id: usize,
+    /// Whether the probe thread was confirmed to run on `cpu_id`.
+    /// If `false`, the data may describe a different core.
+    pub pinned: bool,
+    /// NUMA node the CPU belongs to, if the OS reports one
+    pub numa_node: Option<usize>,
+    /// Running microcode revision, if the OS reports it
+    pub microcode: Option<u64>,
     /// CPU vendor string (e.g., "GenuineIntel")
     pub vendor: String,
     /// CPU brand string (e.g., "Intel(R) Core(TM) i7-9700K CPU @ 3.60GHz")
@@ -164,6 +208,24 @@ pub struct X86CpuInfo {
     pub hybrid: bool,
     /// The type of core, if hybrid
     pub core_type: Option<CoreType>,
+    /// Native model ID from CPUID(0x1A).EAX[23:0], if hybrid
+    pub native_model_id: Option<u32>,
+    /// Cache hierarchy seen by this core, ordered by level
+    pub caches: Vec<CacheInfo>,
+}
+
+impl X86CpuInfo {
+    /// Reads the current cpufreq state of this CPU from the live system, even if
+    /// this info was built from a captured tree; use [`Self::frequency_from`] for those.
+    /// Not cached, since the current frequency and governor change at runtime.
+    pub fn frequency(&self) -> Option<CpuFreq> {
+        cpufreq::read_cpu(self.cpu_id)
+    }
+
+    /// Reads the cpufreq state of this CPU from `src`.
+    pub fn frequency_from(&self, src: &Source) -> Option<CpuFreq> {
+        cpufreq::read_cpu_from(src, self.cpu_id)
+    }
 }
 
 /// Probe info for the current logical CPU (affinity pinned)
@@ -232,22 +294,27 @@ fn gather_core() -> X86CpuInfo {
             let (_, eb, _, _) = cpuid(1, 0);
             (1, (eb >> 16) & 0xff)
         };
-        let cores = if tpc > 0 { tpp / tpc } else { 1 };
+        let cores = tpp.checked_div(tpc).unwrap_or(1);
         let (_, _, _, ed7b) = cpuid(7, 0);
         let hybrid = (ed7b & (1 << 15)) != 0;
-        let core_type = if hybrid {
+        let caches = cpuid_caches(&vendor);
+        let (core_type, native_model_id) = if hybrid && max_l >= 0x1a {
             let (e1a, _, _, _) = cpuid(0x1a, 0);
             let ct = ((e1a >> 24) & 0xff) as u8;
-            Some(if ct == 0x20 {
-                CoreType::Efficiency
-            } else {
-                CoreType::Performance
-            })
+            let has_l3 = caches.iter().any(|c| c.level == 3);
+            (
+                Some(CoreType::from_cpuid(ct, has_l3)),
+                Some(e1a & 0x00ff_ffff),
+            )
         } else {
-            None
+            (None, None)
         };
 
         X86CpuInfo {
+            cpu_id: 0,
+            pinned: false,
+            numa_node: None,
+            microcode: None,
             vendor,
             brand,
             features: f,
@@ -255,40 +322,226 @@ fn gather_core() -> X86CpuInfo {
             threads_per_core: tpc,
             hybrid,
             core_type,
+            native_model_id,
+            caches,
         }
     }
 }
 
-/// One-time probe of every logical CPU in a global cache
-/// Global cache of all logical CPU infos, initialized once at startup.
-/// Uses thread affinity pinning to probe each logical CPU.
-static CPU_INFOS: Lazy<Arc<Vec<X86CpuInfo>>> = Lazy::new(|| {
-    let n = std::thread::available_parallelism().unwrap().get();
-    let mut vec = Vec::with_capacity(n);
-    for cpu in 0..n {
-        let info = thread::Builder::new()
-            .name(format!("cpu_probe_{}", cpu))
-            .spawn(move || {
-                #[cfg(target_os = "linux")]
-                unsafe {
-                    let mut set: cpu_set_t = std::mem::zeroed();
-                    CPU_ZERO(&mut set);
-                    CPU_SET(cpu, &mut set);
-                    pthread_setaffinity_np(pthread_self(), std::mem::size_of::<cpu_set_t>(), &set);
-                }
-                #[cfg(windows)]
-                // Windows thread pinning skipped
-                {}
-                gather_core()
-            })
-            .unwrap()
-            .join()
-            .unwrap();
-        vec.push(info);
+/// Decodes the deterministic cache parameters for the current core.
+/// Uses CPUID(4) on Intel and CPUID(0x8000_001D) on AMD/Hygon with TOPOEXT;
+/// both leaves share the same register layout.
+unsafe fn cpuid_caches(vendor: &str) -> Vec<CacheInfo> {
+    let (max_l, _, _, _) = cpuid(0, 0);
+    let (max_ext, _, _, _) = cpuid(0x8000_0000, 0);
+    let topoext = max_ext >= 0x8000_001d && (cpuid(0x8000_0001, 0).2 & (1 << 22)) != 0;
+    let leaf = match vendor {
+        "AuthenticAMD" | "HygonGenuine" if topoext => 0x8000_001d,
+        _ if max_l >= 4 => 4,
+        _ => return Vec::new(),
+    };
+    let mut caches = Vec::new();
+    for subleaf in 0..32 {
+        let (eax, ebx, ecx, _) = cpuid(leaf, subleaf);
+        let cache_type = match eax & 0x1f {
+            1 => CacheType::Data,
+            2 => CacheType::Instruction,
+            3 => CacheType::Unified,
+            _ => break,
+        };
+        let line_size = (ebx & 0xfff) + 1;
+        let partitions = ((ebx >> 12) & 0x3ff) + 1;
+        let ways = ((ebx >> 22) & 0x3ff) + 1;
+        let sets = ecx.wrapping_add(1);
+        caches.push(CacheInfo {
+            level: ((eax >> 5) & 0x7) as u8,
+            cache_type,
+            size: ways as u64 * partitions as u64 * line_size as u64 * sets as u64,
+            ways,
+            line_size,
+            sets,
+            shared_cpus: Vec::new(),
+        });
     }
-    Arc::new(vec)
+    caches.sort_by_key(|c| c.level);
+    caches
+}
+
+/// Returns the OS IDs of the logical CPUs this process is allowed to run on.
+/// On Linux this is the process affinity mask intersected with the online set,
+/// so taskset/cgroup restrictions and offline CPUs are honoured.
+fn probe_cpu_ids() -> Vec<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let src = Source::live();
+        let online = src.online_cpus();
+        let mut ids = src
+            .allowed_cpus()
+            .or_else(affinity_cpu_ids)
+            .unwrap_or_default();
+        if let Some(online) = &online {
+            if ids.is_empty() {
+                ids = online.clone();
+            } else {
+                ids.retain(|cpu| online.contains(cpu));
+            }
+        }
+        if !ids.is_empty() {
+            return ids;
+        }
+    }
+    let n = thread::available_parallelism().map_or(1, |n| n.get());
+    (0..n).collect()
+}
+
+/// Reads the process's CPU affinity mask when `/proc/self/status` is unavailable.
+/// Asks for the main thread (TID = PID) rather than the calling thread, which may be
+/// pinned. Fails with more than `CPU_SETSIZE` CPUs; the caller then uses the online set.
+#[cfg(target_os = "linux")]
+fn affinity_cpu_ids() -> Option<Vec<usize>> {
+    unsafe {
+        let mut set: cpu_set_t = std::mem::zeroed();
+        if sched_getaffinity(getpid(), std::mem::size_of::<cpu_set_t>(), &mut set) != 0 {
+            return None;
+        }
+        Some(
+            (0..CPU_SETSIZE as usize)
+                .filter(|&cpu| CPU_ISSET(cpu, &set))
+                .collect(),
+        )
+    }
+}
+
+/// Pins the calling thread to `cpu` and reports whether it is now running there.
+/// The mask is sized for `cpu`, so IDs beyond `CPU_SETSIZE` work too.
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) -> bool {
+    let bits = libc::c_ulong::BITS as usize;
+    // Never pass less than a cpu_set_t; the kernel zero-extends a shorter mask anyway
+    let words = (cpu / bits + 1).max(std::mem::size_of::<cpu_set_t>() * 8 / bits);
+    let mut mask: Vec<libc::c_ulong> = vec![0; words];
+    mask[cpu / bits] |= 1 << (cpu % bits);
+    unsafe {
+        pthread_setaffinity_np(
+            pthread_self(),
+            words * std::mem::size_of::<libc::c_ulong>(),
+            mask.as_ptr() as *const cpu_set_t,
+        ) == 0
+            && sched_getcpu() >= 0
+            && sched_getcpu() as usize == cpu
+    }
+}
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_cpu: usize) -> bool {
+    // Thread pinning is only implemented on Linux
+    false
+}
+
+/// Parses a hexadecimal microcode revision such as `"0x2b000603"`.
+pub fn parse_microcode(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let hex = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    u64::from_str_radix(hex, 16).ok()
+}
+
+/// Reads the running microcode revision of a logical CPU from
+/// `cpuN/microcode/version`, falling back to the `microcode` field of the
+/// already-parsed `/proc/cpuinfo` `entries`, so probing many CPUs parses it once.
+pub fn microcode_revision_from(src: &Source, cpu: usize, entries: &[ProcCpuEntry]) -> Option<u64> {
+    let path = format!("{}/cpu{}/microcode/version", sysfs::CPU_ROOT, cpu);
+    if let Some(rev) = src.read_trimmed(&path).and_then(|s| parse_microcode(&s)) {
+        return Some(rev);
+    }
+    entries
+        .iter()
+        .find(|e| e.processor == Some(cpu))
+        .and_then(|e| e.get("microcode"))
+        .and_then(parse_microcode)
+}
+
+/// Probes a single logical CPU on a dedicated thread pinned to it.
+/// `entries` is the parsed `/proc/cpuinfo`, shared by every CPU of a probe run.
+/// Returns `None` if the thread could not be spawned or panicked.
+fn probe_cpu(cpu: usize, entries: &[ProcCpuEntry]) -> Option<X86CpuInfo> {
+    let microcode = microcode_revision_from(&Source::live(), cpu, entries);
+    thread::Builder::new()
+        .name(format!("cpu_probe_{}", cpu))
+        .spawn(move || {
+            let pinned = pin_to_cpu(cpu);
+            let mut info = gather_core();
+            // CPUID only reports how many CPUs share a cache; sysfs knows which
+            let sysfs_caches = cache::read_cpu(cpu);
+            for c in &mut info.caches {
+                if let Some(s) = sysfs_caches
+                    .iter()
+                    .find(|s| s.level == c.level && s.cache_type == c.cache_type)
+                {
+                    c.shared_cpus = s.shared_cpus.clone();
+                }
+            }
+            X86CpuInfo {
+                cpu_id: cpu,
+                pinned,
+                numa_node: numa::node_of_cpu(cpu),
+                microcode,
+                ..info
+            }
+        })
+        .ok()?
+        .join()
+        .ok()
+}
+
+/// Logical CPU infos as probed on first use, sorted by OS CPU ID.
+static INITIAL_CPU_INFOS: Lazy<Arc<Vec<X86CpuInfo>>> = Lazy::new(|| {
+    let entries = Source::live().proc_cpuinfo();
+    Arc::new(
+        probe_cpu_ids()
+            .into_iter()
+            .filter_map(|cpu| probe_cpu(cpu, &entries))
+            .collect(),
+    )
 });
 
+/// Cache of all logical CPU infos, sorted by OS CPU ID.
+/// Starts out as [`INITIAL_CPU_INFOS`] and is replaced by [`refresh`].
+static CPU_INFOS: Lazy<RwLock<Arc<Vec<X86CpuInfo>>>> =
+    Lazy::new(|| RwLock::new(INITIAL_CPU_INFOS.clone()));
+
+/// Serialises [`refresh`] calls, which probe without holding [`CPU_INFOS`].
+static REFRESH: Mutex<()> = Mutex::new(());
+
+/// Senders registered through [`subscribe`].
+static SUBSCRIBERS: Lazy<Mutex<Vec<Sender<TopologyChange>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Describes which logical CPUs appeared or disappeared during a [`refresh`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TopologyChange {
+    /// OS CPU IDs that were probed and added to the cache
+    pub added: Vec<usize>,
+    /// OS CPU IDs that were removed from the cache
+    pub removed: Vec<usize>,
+}
+
+impl TopologyChange {
+    /// Returns `true` if no CPUs were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Returns all cached logical CPU infos as of the last [`refresh`].
+/// The snapshot stays valid and unchanged while later refreshes replace the cache.
+pub fn snapshot() -> Arc<Vec<X86CpuInfo>> {
+    CPU_INFOS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
 /// Initialize the all-core cache; call once at startup
 /// Initializes the global all-core CPU info cache.
 /// Should be called once at program startup for best performance.
@@ -296,6 +549,119 @@ pub fn init_all_core_cache() {
     Lazy::force(&CPU_INFOS);
 }
 
+/// Re-reads the set of usable CPUs and updates the cache.
+/// Only CPUs that came online (or entered the affinity mask) are probed; CPUs that
+/// went away are dropped. Subscribers are notified if anything changed.
+pub fn refresh() -> TopologyChange {
+    let _guard = REFRESH.lock().unwrap_or_else(PoisonError::into_inner);
+    let ids = probe_cpu_ids();
+    // Probe against a snapshot so readers are never blocked behind probe threads
+    let cache = snapshot();
+    let mut change = TopologyChange::default();
+    let mut infos = Vec::with_capacity(ids.len());
+    let mut entries = None;
+    for &cpu in &ids {
+        match cache.binary_search_by_key(&cpu, |info| info.cpu_id) {
+            Ok(i) => infos.push(cache[i].clone()),
+            Err(_) => {
+                let entries = entries.get_or_insert_with(|| Source::live().proc_cpuinfo());
+                if let Some(info) = probe_cpu(cpu, entries) {
+                    infos.push(info);
+                    change.added.push(cpu);
+                }
+            }
+        }
+    }
+    change.removed = cache
+        .iter()
+        .map(|info| info.cpu_id)
+        .filter(|cpu| ids.binary_search(cpu).is_err())
+        .collect();
+    if !change.is_empty() {
+        *CPU_INFOS.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(infos);
+        SUBSCRIBERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|tx| tx.send(change.clone()).is_ok());
+    }
+    change
+}
+
+/// Registers for notifications of topology changes detected by [`refresh`]
+/// (including refreshes triggered by [`watch_hotplug`]).
+/// The subscription ends when the returned receiver is dropped.
+pub fn subscribe() -> Receiver<TopologyChange> {
+    let (tx, rx) = mpsc::channel();
+    SUBSCRIBERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(tx);
+    rx
+}
+
+/// Background thread that polls the kernel's online CPU list and calls [`refresh`]
+/// when it changes. Stops when [`HotplugWatcher::stop`] is called or it is dropped.
+#[cfg(target_os = "linux")]
+pub struct HotplugWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+#[cfg(target_os = "linux")]
+impl HotplugWatcher {
+    /// Stops the watcher and waits for its thread to exit.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            // Wake the thread from its wait so it sees the flag right away
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for HotplugWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Starts polling `/sys/devices/system/cpu/online` every `interval` and refreshes
+/// the cache whenever its contents change.
+#[cfg(target_os = "linux")]
+pub fn watch_hotplug(interval: Duration) -> HotplugWatcher {
+    init_all_core_cache();
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    let handle = thread::Builder::new()
+        .name("cpu_hotplug_watcher".to_string())
+        .spawn(move || {
+            let src = Source::live();
+            let mut last = src.read_trimmed(sysfs::CPU_ONLINE);
+            loop {
+                thread::park_timeout(interval);
+                if flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let online = src.read_trimmed(sysfs::CPU_ONLINE);
+                if online != last {
+                    refresh();
+                    last = online;
+                }
+            }
+        })
+        .unwrap();
+    HotplugWatcher {
+        stop,
+        handle: Some(handle),
+    }
+}
+
 /// Helper: get current logical CPU index
 #[cfg(target_os = "linux")]
 /// Returns the index of the current logical CPU.
@@ -314,44 +680,114 @@ fn current_cpu_id() -> usize {
 
 /// Return the info for the current logical CPU
 /// Returns the `X86CpuInfo` for the current logical CPU.
-/// Falls back to the first CPU if the index is out of bounds.
+/// Falls back to the first probed CPU if the current one was not probed.
 pub fn gather() -> X86CpuInfo {
-    let idx = current_cpu_id();
-    CPU_INFOS
-        .get(idx)
-        .cloned()
-        .unwrap_or_else(|| CPU_INFOS[0].clone())
+    info_for_cpu(current_cpu_id()).unwrap_or_else(|| snapshot()[0].clone())
 }
 
-/// Lookup cached info for any logical CPU index
-/// Looks up cached info for any logical CPU index.
-/// Returns `Some(X86CpuInfo)` if the index is valid, otherwise `None`.
-pub fn info_for_cpu(idx: usize) -> Option<X86CpuInfo> {
-    CPU_INFOS.get(idx).cloned()
+/// Lookup cached info for any OS CPU ID
+/// Looks up cached info for the logical CPU with the given OS CPU ID.
+/// Returns `None` if that CPU was not probed (offline or outside the affinity mask).
+pub fn info_for_cpu(cpu_id: usize) -> Option<X86CpuInfo> {
+    let infos = snapshot();
+    infos
+        .binary_search_by_key(&cpu_id, |info| info.cpu_id)
+        .ok()
+        .map(|i| infos[i].clone())
 }
 
-/// Return a slice of all cached CPU infos
-/// Returns a slice of all cached logical CPU infos.
-/// Ensures the cache is initialized before returning.
+/// Return a snapshot of all cached CPU infos
+/// Returns all logical CPU infos as probed at startup.
+/// Ensures the cache is initialized before returning; use [`snapshot`] to see
+/// CPUs added or removed by [`refresh`].
 pub fn all_cpuinfos() -> &'static [X86CpuInfo] {
-    init_all_core_cache();
-    &*CPU_INFOS
+    &INITIAL_CPU_INFOS
 }
 
 /// Print all cached CPU infos to stdout, with count and separators
 /// Prints all cached logical CPU infos to stdout, with count and separators.
 /// Useful for debugging and inspection.
 pub fn print_all_cpuinfos() {
-    // Ensure cache is populated
-    init_all_core_cache();
-    let infos = &*CPU_INFOS;
+    let infos = snapshot();
     println!("Found {} logical CPUs:", infos.len());
-    for (i, info) in infos.iter().enumerate() {
-        println!("--- CPU {} ---", i);
+    for info in infos.iter() {
+        println!("--- CPU {} ---", info.cpu_id);
         println!("{}", info);
     }
 }
 
+/// Sysfs list of CPUs served by the P-core PMU on Linux hybrid systems.
+const SYSFS_CPU_CORE: &str = "/sys/devices/cpu_core/cpus";
+/// Sysfs list of CPUs served by the E-core (Atom) PMU on Linux hybrid systems.
+const SYSFS_CPU_ATOM: &str = "/sys/devices/cpu_atom/cpus";
+
+/// System-wide inventory of hybrid core types, by OS CPU ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HybridInventory {
+    /// CPUs reporting P-cores
+    pub performance: Vec<usize>,
+    /// CPUs reporting E-cores
+    pub efficiency: Vec<usize>,
+    /// CPUs reporting low-power E-cores
+    pub low_power_efficiency: Vec<usize>,
+    /// CPUs reporting an unrecognised core type, or none at all
+    pub unknown: Vec<usize>,
+    /// Contents of `/sys/devices/cpu_core/cpus`, if present
+    pub sysfs_core: Option<Vec<usize>>,
+    /// Contents of `/sys/devices/cpu_atom/cpus`, if present
+    pub sysfs_atom: Option<Vec<usize>>,
+}
+
+impl HybridInventory {
+    /// Returns the CPUs whose CPUID core type disagrees with the kernel's
+    /// `cpu_core`/`cpu_atom` PMU lists. Empty if sysfs does not expose them.
+    pub fn mismatches(&self) -> Vec<usize> {
+        let (Some(core), Some(atom)) = (&self.sysfs_core, &self.sysfs_atom) else {
+            return Vec::new();
+        };
+        let mut out: Vec<usize> = self
+            .performance
+            .iter()
+            .filter(|cpu| !core.contains(cpu))
+            .chain(
+                self.efficiency
+                    .iter()
+                    .chain(&self.low_power_efficiency)
+                    .filter(|cpu| !atom.contains(cpu)),
+            )
+            .copied()
+            .collect();
+        out.sort_unstable();
+        out
+    }
+}
+
+/// Builds a [`HybridInventory`] from the cached per-CPU infos and cross-checks it
+/// against the Linux hybrid PMU lists.
+pub fn hybrid_inventory() -> HybridInventory {
+    hybrid_inventory_from(&Source::live(), &snapshot())
+}
+
+/// Builds a [`HybridInventory`] from `infos` and the PMU lists found in `src`.
+pub fn hybrid_inventory_from(src: &Source, infos: &[X86CpuInfo]) -> HybridInventory {
+    let mut inv = HybridInventory::default();
+    for info in infos {
+        match info.core_type {
+            Some(CoreType::Performance) => inv.performance.push(info.cpu_id),
+            Some(CoreType::Efficiency) => inv.efficiency.push(info.cpu_id),
+            Some(CoreType::LowPowerEfficiency) => inv.low_power_efficiency.push(info.cpu_id),
+            Some(CoreType::Unknown(_)) | None => inv.unknown.push(info.cpu_id),
+        }
+    }
+    inv.sysfs_core = src
+        .read_trimmed(SYSFS_CPU_CORE)
+        .map(|s| sysfs::parse_cpu_list(&s));
+    inv.sysfs_atom = src
+        .read_trimmed(SYSFS_CPU_ATOM)
+        .map(|s| sysfs::parse_cpu_list(&s));
+    inv
+}
+
 impl fmt::Display for X86CpuInfo {
     /// Formats the CPU info for pretty-printing.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
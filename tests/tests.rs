@@ -53,3 +53,1168 @@ fn test_has_feature_with_unknown_feature() {
         assert!(!info.has_feature(InstructionSet::SSE));
     }
 }
+
+#[test]
+fn test_parse_cpu_list() {
+    use cpuidrs::sysfs::parse_cpu_list;
+    assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+    assert_eq!(parse_cpu_list("5"), vec![5]);
+    assert!(parse_cpu_list("").is_empty());
+}
+
+#[test]
+fn test_allowed_cpus_from_process_status() {
+    use cpuidrs::sysfs::Source;
+    // The process mask, beyond the 1024 CPUs a cpu_set_t can describe
+    let src = Source::memory([(
+        "/proc/self/status",
+        "Name:\tprobe\nCpus_allowed:\tff\nCpus_allowed_list:\t0-1,1030\nMems_allowed_list:\t0\n",
+    )]);
+    assert_eq!(src.allowed_cpus(), Some(vec![0, 1, 1030]));
+    assert_eq!(
+        Source::memory([("/proc/self/status", "Name:\tprobe\n")]).allowed_cpus(),
+        None
+    );
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_x86_cache_is_keyed_by_cpu_id() {
+    use cpuidrs::x86;
+    let all = x86::all_cpuinfos();
+    assert!(!all.is_empty());
+    assert!(all.windows(2).all(|w| w[0].cpu_id < w[1].cpu_id));
+    for info in all.iter() {
+        assert_eq!(x86::info_for_cpu(info.cpu_id).unwrap().cpu_id, info.cpu_id);
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_x86_refresh_without_hotplug_is_empty() {
+    use cpuidrs::x86;
+    let before = x86::snapshot();
+    let rx = x86::subscribe();
+    assert!(x86::refresh().is_empty());
+    assert!(rx.try_recv().is_err());
+    assert_eq!(x86::snapshot().len(), before.len());
+    assert_eq!(x86::all_cpuinfos().len(), before.len());
+}
+
+#[cfg(all(target_os = "linux", any(target_arch = "x86", target_arch = "x86_64")))]
+#[test]
+fn test_x86_hotplug_watcher_stops_promptly() {
+    use std::time::{Duration, Instant};
+    let watcher = cpuidrs::x86::watch_hotplug(Duration::from_secs(60));
+    let start = Instant::now();
+    drop(watcher);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_x86_core_type_decoding_and_inventory() {
+    use cpuidrs::x86::{self, CoreType};
+    assert_eq!(CoreType::from_cpuid(0x40, true), CoreType::Performance);
+    assert_eq!(CoreType::from_cpuid(0x20, true), CoreType::Efficiency);
+    assert_eq!(
+        CoreType::from_cpuid(0x20, false),
+        CoreType::LowPowerEfficiency
+    );
+    assert_eq!(CoreType::from_cpuid(0x10, true), CoreType::Unknown(0x10));
+
+    let inv = x86::hybrid_inventory();
+    let listed = inv.performance.len()
+        + inv.efficiency.len()
+        + inv.low_power_efficiency.len()
+        + inv.unknown.len();
+    assert_eq!(listed, x86::snapshot().len());
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_x86_hybrid_inventory_from_captured_tree() {
+    use cpuidrs::sysfs::Source;
+    use cpuidrs::x86::{self, CoreType, X86CpuInfo};
+    let base = x86::gather();
+    let cpu = |cpu_id, core_type| X86CpuInfo {
+        cpu_id,
+        core_type,
+        ..base.clone()
+    };
+    // CPU 2 is an E-core by CPUID, but the kernel lists it under cpu_core
+    let infos = [
+        cpu(0, Some(CoreType::Performance)),
+        cpu(1, Some(CoreType::Performance)),
+        cpu(2, Some(CoreType::Efficiency)),
+        cpu(3, Some(CoreType::LowPowerEfficiency)),
+        cpu(4, None),
+    ];
+    let src = Source::memory([
+        ("/sys/devices/cpu_core/cpus", "0-2\n"),
+        ("/sys/devices/cpu_atom/cpus", "3\n"),
+    ]);
+    let inv = x86::hybrid_inventory_from(&src, &infos);
+    assert_eq!(inv.performance, vec![0, 1]);
+    assert_eq!(inv.low_power_efficiency, vec![3]);
+    assert_eq!(inv.unknown, vec![4]);
+    assert_eq!(inv.sysfs_core, Some(vec![0, 1, 2]));
+    assert_eq!(inv.mismatches(), vec![2]);
+    assert!(
+        x86::hybrid_inventory_from(&Source::memory(Vec::<(String, String)>::new()), &infos)
+            .mismatches()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_topology_tree_from_cpu_records() {
+    use cpuidrs::topology::{CpuTopology, Topology};
+    let cpu = |cpu_id, package_id, core_id, siblings: &[usize]| CpuTopology {
+        cpu_id,
+        package_id,
+        die_id: 0,
+        cluster_id: -1,
+        core_id,
+        thread_siblings: siblings.to_vec(),
+        cluster_cpus: Vec::new(),
+        die_cpus: Vec::new(),
+        package_cpus: Vec::new(),
+    };
+    // Two sockets, two SMT cores each
+    let topo = Topology::from_cpus(vec![
+        cpu(0, 0, 0, &[0, 4]),
+        cpu(1, 0, 1, &[1, 5]),
+        cpu(2, 1, 0, &[2, 6]),
+        cpu(3, 1, 1, &[3, 7]),
+        cpu(4, 0, 0, &[0, 4]),
+        cpu(5, 0, 1, &[1, 5]),
+        cpu(6, 1, 0, &[2, 6]),
+        cpu(7, 1, 1, &[3, 7]),
+    ]);
+    assert_eq!(topo.package_count(), 2);
+    assert_eq!(topo.core_count(), 4);
+    assert_eq!(topo.thread_count(), 8);
+    assert_eq!(topo.threads_per_core(), 2);
+    assert_eq!(
+        topo.packages[1].dies[0].clusters[0].cores[0].threads,
+        vec![2, 6]
+    );
+    assert_eq!(topo.cpu(5).unwrap().thread_siblings, vec![1, 5]);
+}
+
+#[test]
+fn test_topology_read_matches_online_cpus() {
+    if let Some(topo) = cpuidrs::topology::read() {
+        let online = cpuidrs::sysfs::online_cpus().unwrap();
+        assert_eq!(topo.thread_count(), online.len());
+        assert!(topo.core_count() >= 1);
+    }
+}
+
+#[test]
+fn test_numa_meminfo_and_lookup() {
+    use cpuidrs::numa::{parse_node_meminfo, NumaNode, NumaTopology};
+    let meminfo = "Node 1 MemTotal:       5471992 kB\nNode 1 MemFree:         3296192 kB\n";
+    assert_eq!(parse_node_meminfo(meminfo), (Some(5471992), Some(3296192)));
+
+    let topo = NumaTopology {
+        nodes: vec![
+            NumaNode {
+                id: 0,
+                cpus: vec![0, 1],
+                distances: vec![10, 21],
+                ..Default::default()
+            },
+            NumaNode {
+                id: 1,
+                cpus: vec![2, 3],
+                distances: vec![21, 10],
+                ..Default::default()
+            },
+        ],
+        online: vec![0, 1],
+    };
+    assert_eq!(topo.node_of_cpu(3), Some(1));
+    assert_eq!(topo.cpus_of_node(0), Some(&[0, 1][..]));
+    assert_eq!(topo.distance(0, 1), Some(21));
+    assert_eq!(topo.node_of_cpu(9), None);
+}
+
+#[test]
+fn test_numa_distance_skips_unreadable_nodes() {
+    use cpuidrs::numa;
+    use cpuidrs::sysfs::Source;
+
+    // node1 has no cpulist, so only nodes 0 and 2 are read
+    let src = Source::memory([
+        ("/sys/devices/system/node/online", "0-2\n"),
+        ("/sys/devices/system/node/node0/cpulist", "0-1\n"),
+        ("/sys/devices/system/node/node0/distance", "10 20 30\n"),
+        ("/sys/devices/system/node/node2/cpulist", "2-3\n"),
+        ("/sys/devices/system/node/node2/distance", "30 20 10\n"),
+    ]);
+    let topo = numa::read_from(&src).unwrap();
+    assert_eq!(topo.nodes.len(), 2);
+    assert_eq!(topo.online, vec![0, 1, 2]);
+    assert_eq!(topo.distance(0, 2), Some(30));
+    assert_eq!(topo.distance(2, 1), Some(20));
+    assert_eq!(topo.distance(2, 2), Some(10));
+    assert_eq!(numa::node_of_cpu_from(&src, 3), Some(2));
+}
+
+#[test]
+fn test_numa_read_covers_cpu_links() {
+    if let Some(topo) = cpuidrs::numa::read() {
+        for node in &topo.nodes {
+            for &cpu in &node.cpus {
+                assert_eq!(cpuidrs::numa::node_of_cpu(cpu), Some(node.id));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cache_size_parsing() {
+    use cpuidrs::cache::parse_size;
+    assert_eq!(parse_size("48K"), Some(48 * 1024));
+    assert_eq!(parse_size("2M\n"), Some(2 * 1024 * 1024));
+    assert_eq!(parse_size("512"), Some(512));
+    assert_eq!(parse_size("K"), None);
+}
+
+#[test]
+fn test_caches_accessor_is_ordered_by_level() {
+    let info = get_cpu_info();
+    let levels: Vec<u8> = info.caches().iter().map(|c| c.level).collect();
+    assert!(levels.windows(2).all(|w| w[0] <= w[1]));
+    for c in info.caches() {
+        assert!(c.level >= 1);
+    }
+}
+
+#[test]
+fn test_cpufreq_read_from_captured_tree() {
+    use cpuidrs::cpufreq::{boost_enabled_from, read_cpu_from};
+    use cpuidrs::sysfs::Source;
+
+    let dir = "/sys/devices/system/cpu/cpu2/cpufreq";
+    let src = Source::memory([
+        (format!("{}/cpuinfo_min_freq", dir), "400000\n".to_string()),
+        (format!("{}/cpuinfo_max_freq", dir), "4700000\n".to_string()),
+        (format!("{}/base_frequency", dir), "2100000\n".to_string()),
+        (format!("{}/scaling_cur_freq", dir), "1800000\n".to_string()),
+        (format!("{}/scaling_max_freq", dir), "4700000\n".to_string()),
+        (
+            format!("{}/scaling_driver", dir),
+            "intel_pstate\n".to_string(),
+        ),
+        (
+            format!("{}/scaling_governor", dir),
+            "powersave\n".to_string(),
+        ),
+        (
+            "/sys/devices/system/cpu/intel_pstate/no_turbo".to_string(),
+            "1\n".to_string(),
+        ),
+    ]);
+    let freq = read_cpu_from(&src, 2).unwrap();
+    assert_eq!(
+        (freq.min_khz, freq.base_khz, freq.max_khz),
+        (Some(400000), Some(2100000), Some(4700000))
+    );
+    assert_eq!(freq.current_khz, Some(1800000));
+    assert_eq!(
+        (freq.scaling_min_khz, freq.scaling_max_khz),
+        (None, Some(4700000))
+    );
+    assert_eq!(freq.driver.as_deref(), Some("intel_pstate"));
+    assert_eq!(freq.governor.as_deref(), Some("powersave"));
+    assert_eq!(read_cpu_from(&src, 0), None);
+    // Infos built from a captured tree read the same tree, not the host
+    let info = cpuidrs::riscv::gather_from(&src, 2);
+    assert_eq!(info.frequency_from(&src), Some(freq));
+    assert_eq!(boost_enabled_from(&src), Some(false));
+
+    // The generic switch wins over intel_pstate's
+    let src = Source::memory([
+        ("/sys/devices/system/cpu/cpufreq/boost", "1\n"),
+        ("/sys/devices/system/cpu/intel_pstate/no_turbo", "1\n"),
+    ]);
+    assert_eq!(boost_enabled_from(&src), Some(true));
+    assert_eq!(
+        boost_enabled_from(&Source::memory([("/proc/cpuinfo", "")])),
+        None
+    );
+}
+
+#[test]
+fn test_parse_proc_cpuinfo_blocks() {
+    use cpuidrs::sysfs::parse_proc_cpuinfo;
+    let text =
+        "processor\t: 0\nmicrocode\t: 0x2b000603\n\nprocessor\t: 1\nmodel name\t: Foo: Bar\n";
+    let entries = parse_proc_cpuinfo(text);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].processor, Some(0));
+    assert_eq!(entries[0].get("microcode"), Some("0x2b000603"));
+    assert_eq!(entries[1].get("model name"), Some("Foo: Bar"));
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_x86_microcode_parsing() {
+    use cpuidrs::x86::{microcode_revision_from, parse_microcode};
+    assert_eq!(parse_microcode("0x2b000603\n"), Some(0x2b000603));
+    assert_eq!(parse_microcode("a201016"), Some(0xa201016));
+    assert_eq!(parse_microcode("none"), None);
+
+    // sysfs wins; otherwise the shared, already-parsed /proc/cpuinfo entries are used
+    let src = cpuidrs::sysfs::Source::memory([
+        (
+            "/sys/devices/system/cpu/cpu0/microcode/version",
+            "0x2b000603\n",
+        ),
+        (
+            "/proc/cpuinfo",
+            "processor\t: 0\nmicrocode\t: 0x1\n\nprocessor\t: 1\nmicrocode\t: 0xa201016\n",
+        ),
+    ]);
+    let entries = src.proc_cpuinfo();
+    assert_eq!(microcode_revision_from(&src, 0, &entries), Some(0x2b000603));
+    assert_eq!(microcode_revision_from(&src, 1, &entries), Some(0xa201016));
+    assert_eq!(microcode_revision_from(&src, 2, &entries), None);
+}
+
+#[test]
+fn test_vulnerability_status_parsing() {
+    use cpuidrs::vulnerabilities::{VulnStatus, Vulnerabilities};
+    let v = Vulnerabilities::from_entries([
+        ("meltdown", "Not affected\n"),
+        (
+            "spectre_v2",
+            "Mitigation: Enhanced / Automatic IBRS; IBPB: conditional",
+        ),
+        (
+            "mds",
+            "Vulnerable: Clear CPU buffers attempted, no microcode; SMT vulnerable",
+        ),
+        ("l1tf", "Vulnerable"),
+        ("ghostwrite", "Unknown: Dependent on hypervisor status"),
+        ("itlb_multihit", "KVM: Mitigation: VMX disabled"),
+        ("srbds", "Processor vulnerable"),
+    ]);
+    assert_eq!(
+        v.itlb_multihit,
+        Some(VulnStatus::Mitigated("VMX disabled".to_string()))
+    );
+    assert_eq!(
+        VulnStatus::parse("KVM: Mitigation: Split huge pages\n"),
+        VulnStatus::Mitigated("Split huge pages".to_string())
+    );
+    assert_eq!(v.srbds, Some(VulnStatus::Vulnerable(None)));
+    assert_eq!(
+        VulnStatus::parse("Processor vulnerable"),
+        VulnStatus::Vulnerable(None)
+    );
+    assert_eq!(v.meltdown, Some(VulnStatus::NotAffected));
+    assert_eq!(
+        v.spectre_v2,
+        Some(VulnStatus::Mitigated(
+            "Enhanced / Automatic IBRS; IBPB: conditional".to_string()
+        ))
+    );
+    assert_eq!(v.l1tf, Some(VulnStatus::Vulnerable(None)));
+    assert_eq!(v.spectre_v1, None);
+    assert_eq!(
+        v.get("ghostwrite"),
+        Some(&VulnStatus::Unknown(
+            "Unknown: Dependent on hypervisor status".to_string()
+        ))
+    );
+    assert_eq!(v.vulnerable(), vec!["l1tf", "mds", "srbds"]);
+}
+
+/// A captured two-core aarch64 system with a shared L2.
+fn captured_arm_source() -> cpuidrs::sysfs::Source {
+    let mut files = vec![
+        (
+            "/proc/cpuinfo".to_string(),
+            "processor\t: 0\nBogoMIPS\t: 50.00\nCPU part\t: 0xd0c\n\n\
+             processor\t: 1\nBogoMIPS\t: 50.00\nCPU part\t: 0xd0c\n\n\
+             Hardware\t: Test Board\n"
+                .to_string(),
+        ),
+        (
+            "/sys/devices/system/cpu/online".to_string(),
+            "0-1\n".to_string(),
+        ),
+    ];
+    for cpu in 0..2 {
+        let dir = format!("/sys/devices/system/cpu/cpu{}", cpu);
+        for (name, val) in [
+            ("topology/core_id", cpu.to_string()),
+            ("topology/physical_package_id", "0".to_string()),
+            ("topology/thread_siblings_list", cpu.to_string()),
+            ("cache/index0/level", "1".to_string()),
+            ("cache/index0/type", "Data".to_string()),
+            ("cache/index0/size", "64K".to_string()),
+            ("cache/index1/level", "2".to_string()),
+            ("cache/index1/type", "Unified".to_string()),
+            ("cache/index1/size", "1024K".to_string()),
+            ("cache/index1/shared_cpu_list", "0-1".to_string()),
+        ] {
+            files.push((format!("{}/{}", dir, name), val));
+        }
+    }
+    cpuidrs::sysfs::Source::memory(files)
+}
+
+#[test]
+fn test_memory_source_lists_directories() {
+    let src = captured_arm_source();
+    assert_eq!(
+        src.list_dir("/sys/devices/system/cpu/cpu0/cache"),
+        vec!["index0", "index1"]
+    );
+    assert_eq!(src.online_cpus(), Some(vec![0, 1]));
+    assert!(src.read("/proc/missing").is_none());
+}
+
+#[test]
+fn test_arm_gather_from_captured_tree() {
+    let info = cpuidrs::arm::gather_from(&captured_arm_source(), 1);
+    assert_eq!(info.vendor, "Test Board");
+    assert_eq!(info.cores, 2);
+    assert_eq!(info.threads_per_core, 1);
+    assert_eq!(info.caches.len(), 2);
+    assert_eq!(info.caches[1].size, 1024 * 1024);
+    assert_eq!(info.caches[1].shared_cpus, vec![0, 1]);
+}
+
+#[test]
+fn test_arm_midr_decoding() {
+    use cpuidrs::arm::midr::{self, Midr};
+    let src = cpuidrs::sysfs::Source::memory([
+        (
+            "/proc/cpuinfo",
+            "processor\t: 0\nCPU implementer\t: 0x41\nCPU architecture: 8\n\
+             CPU variant\t: 0x2\nCPU part\t: 0xd05\nCPU revision\t: 0\n\n\
+             processor\t: 1\nCPU implementer\t: 0x41\nCPU architecture: 8\n\
+             CPU variant\t: 0x4\nCPU part\t: 0xd0b\nCPU revision\t: 1\n\n\
+             Hardware\t: Test Board\n",
+        ),
+        (
+            "/sys/devices/system/cpu/cpu1/regs/identification/midr_el1",
+            "0x00000000413fd0c1\n",
+        ),
+    ]);
+    let little = midr::read_cpu_from(&src, 0).unwrap();
+    assert_eq!(little.part_name(), Some("Cortex-A55"));
+    assert_eq!(little.to_string(), "ARM Cortex-A55 r2p0");
+    // sysfs takes precedence over /proc/cpuinfo
+    let big = midr::read_cpu_from(&src, 1).unwrap();
+    assert_eq!(big, Midr::from_raw(0x413f_d0c1));
+    assert_eq!(big.raw(), 0x413f_d0c1);
+    assert_eq!((big.variant, big.revision), (3, 1));
+    assert!(midr::read_cpu_from(&src, 2).is_none());
+
+    let info = cpuidrs::arm::gather_from(&src, 1);
+    assert_eq!(info.vendor, "ARM");
+    assert_eq!(info.brand, "Neoverse N1");
+    assert_eq!(
+        Midr::from_raw(0x611f_0221).to_string(),
+        "Apple M1 Icestorm r1p1"
+    );
+    assert_eq!(midr::part_name(0x51, 0x001), Some("Oryon"));
+}
+
+#[test]
+fn test_arm_id_register_decoding() {
+    use cpuidrs::arm::idregs::{AtomicOps, IdRegisters, PacAlgorithm, SveVersion};
+    use cpuidrs::arm::ArmFeatures;
+    // Register values recorded on a Neoverse N1
+    let n1 = IdRegisters {
+        isar0: 0x0000_1000_1021_1120,
+        isar1: 0x0000_0000_0010_0001,
+        pfr0: 0x1100_0000_1011_1111,
+        pfr1: 0x0000_0000_0000_0020,
+        mmfr0: 0x0000_0000_0010_1125,
+        mmfr2: 0x0000_0001_0000_0000,
+        ..Default::default()
+    };
+    let f = n1.features();
+    assert!(f.contains(
+        ArmFeatures::FP
+            | ArmFeatures::FPHP
+            | ArmFeatures::NEON
+            | ArmFeatures::ASIMDHP
+            | ArmFeatures::AES
+            | ArmFeatures::PMULL
+            | ArmFeatures::SHA2
+            | ArmFeatures::CRC32
+            | ArmFeatures::ATOMICS
+            | ArmFeatures::ASIMDRDM
+            | ArmFeatures::ASIMDDP
+            | ArmFeatures::DCPOP
+            | ArmFeatures::LRCPC
+            | ArmFeatures::SSBS
+            | ArmFeatures::USCAT
+    ));
+    assert!(!f.intersects(ArmFeatures::SVE | ArmFeatures::PACA | ArmFeatures::SHA512));
+    let ext = n1.extensions();
+    assert_eq!(ext.atomics, AtomicOps::Lse);
+    assert_eq!(
+        (ext.sve, ext.pac, ext.lrcpc, ext.pa_bits),
+        (None, None, 1, Some(48))
+    );
+    let emulated = IdRegisters {
+        emulated: true,
+        ..n1
+    };
+    assert_eq!(emulated.extensions().pa_bits, None);
+
+    // SVE2 with QARMA3 pointer authentication and MTE3
+    let v2 = IdRegisters {
+        isar1: 0x0000_0000_0100_0000,
+        isar2: 0x0000_0000_0000_5100,
+        pfr0: 0x0000_0001_0000_0000,
+        pfr1: 0x0000_0000_0000_0301,
+        zfr0: 0x0000_0000_0000_0001,
+        ..Default::default()
+    };
+    let f = v2.features();
+    assert!(
+        f.contains(ArmFeatures::SVE | ArmFeatures::SVE2 | ArmFeatures::PACA | ArmFeatures::PACG)
+    );
+    assert!(f.contains(ArmFeatures::BTI | ArmFeatures::MTE | ArmFeatures::MTE3));
+    let ext = v2.extensions();
+    assert_eq!(ext.sve, Some(SveVersion::Sve2));
+    assert_eq!((ext.pac, ext.pauth_level), (Some(PacAlgorithm::Qarma3), 5));
+    assert_eq!((ext.mte, ext.bti), (3, true));
+}
+
+#[test]
+fn test_arm_per_cpu_core_classes() {
+    use cpuidrs::arm::CoreClass;
+    // One Cortex-X1, two Cortex-A76 and one Cortex-A55, each cluster reported separately
+    let cores = [
+        (0xd05, 0, 400),
+        (0xd0b, 1, 800),
+        (0xd0b, 1, 800),
+        (0xd44, 2, 1024),
+    ];
+    let mut files = vec![
+        (
+            "/sys/devices/system/cpu/online".to_string(),
+            "0-3".to_string(),
+        ),
+        (
+            "/sys/devices/system/node/online".to_string(),
+            "0-1".to_string(),
+        ),
+        (
+            "/sys/devices/system/node/node0/cpulist".to_string(),
+            "0-1".to_string(),
+        ),
+        (
+            "/sys/devices/system/node/node1/cpulist".to_string(),
+            "2-3".to_string(),
+        ),
+    ];
+    for (cpu, (part, cluster, capacity)) in cores.into_iter().enumerate() {
+        let dir = format!("/sys/devices/system/cpu/cpu{}", cpu);
+        for (name, val) in [
+            (
+                "regs/identification/midr_el1",
+                format!("0x{:016x}", 0x410f_0000 | (part << 4)),
+            ),
+            ("cpu_capacity", capacity.to_string()),
+            ("topology/core_id", cpu.to_string()),
+            ("topology/cluster_id", cluster.to_string()),
+        ] {
+            files.push((format!("{}/{}", dir, name), val));
+        }
+    }
+    let infos = cpuidrs::arm::gather_all_from(&cpuidrs::sysfs::Source::memory(files));
+    assert_eq!(infos.len(), 4);
+    let summary: Vec<_> = infos
+        .iter()
+        .map(|i| (i.cpu_id, i.brand.as_str(), i.cluster_id, i.core_class))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (0, "Cortex-A55", 0, Some(CoreClass::Little)),
+            (1, "Cortex-A76", 1, Some(CoreClass::Mid)),
+            (2, "Cortex-A76", 1, Some(CoreClass::Mid)),
+            (3, "Cortex-X1", 2, Some(CoreClass::Big)),
+        ]
+    );
+    assert_eq!(infos[3].cpu_capacity, Some(1024));
+    let nodes: Vec<_> = infos.iter().map(|i| i.numa_node).collect();
+    assert_eq!(nodes, [Some(0), Some(0), Some(1), Some(1)]);
+    assert_eq!(CoreClass::from_capacity(1024, &[1024, 1024]), None);
+}
+
+#[test]
+fn test_arm_brand_from_own_cpuinfo_block() {
+    // No MIDR anywhere: each CPU is named by its own block, the board by the trailing line
+    let src = cpuidrs::sysfs::Source::memory([(
+        "/proc/cpuinfo",
+        "processor\t: 0\nmodel name\t: Little core\n\n\
+         processor\t: 1\nmodel name\t: Big core\n\n\
+         Hardware\t: Example board\n",
+    )]);
+    let infos = cpuidrs::arm::gather_all_from(&src);
+    let names: Vec<_> = infos
+        .iter()
+        .map(|i| (i.vendor.as_str(), i.brand.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("Example board", "Little core"),
+            ("Example board", "Big core")
+        ]
+    );
+}
+
+#[test]
+fn test_arm_vector_lengths() {
+    use cpuidrs::arm::vector::{self, VectorKind};
+    // 256-bit vectors with PR_SVE_VL_INHERIT set
+    assert_eq!(vector::decode_prctl_vl((1 << 17) | 32), Some(32));
+    assert_eq!(vector::decode_prctl_vl(-1), None);
+    let src = cpuidrs::sysfs::Source::memory([("/proc/sys/abi/sve_default_vector_length", "64\n")]);
+    assert_eq!(vector::read_default_from(&src, VectorKind::Sve), Some(64));
+    assert_eq!(vector::read_default_from(&src, VectorKind::Sme), None);
+    assert!(cpuidrs::arm::gather_from(&src, 0).sve_lengths.is_none());
+}
+
+#[test]
+fn test_arm_cpuinfo_features_line() {
+    use cpuidrs::arm::{ArmFeatures, FeatureMismatch};
+    let n1 = ArmFeatures::from_cpuinfo_features(
+        "fp asimd evtstrm aes pmull sha1 sha2 crc32 atomics fphp asimdhp cpuid asimdrdm lrcpc dcpop asimddp ssbs",
+    );
+    assert_eq!(n1, ArmFeatures::from_hwcaps(0x1011_9fff, 0, 0));
+    let sme = ArmFeatures::from_cpuinfo_features("sme smei16i64 smefa64 mtestoreonly");
+    assert_eq!(
+        sme,
+        ArmFeatures::SME
+            | ArmFeatures::SME_I16I64
+            | ArmFeatures::SME_FA64
+            | ArmFeatures::MTE_STORE_ONLY
+    );
+    // 32-bit names, with unknown ones such as "edsp" and "tls" ignored
+    let v7 = ArmFeatures::from_cpuinfo_features(
+        "half thumb fastmult vfp edsp neon vfpv3 tls vfpv4 idiva lpae",
+    );
+    assert!(v7
+        .contains(ArmFeatures::NEON | ArmFeatures::VFPV4 | ArmFeatures::IDIVA | ArmFeatures::LPAE));
+    assert_eq!(v7.iter().count(), 9);
+
+    let src = cpuidrs::sysfs::Source::memory([(
+        "/proc/cpuinfo",
+        "processor\t: 0\nFeatures\t: fp asimd aes\n\nprocessor\t: 1\nFeatures\t: fp asimd aes crc32\n",
+    )]);
+    let info = cpuidrs::arm::gather_from(&src, 1);
+    assert_eq!(
+        info.features,
+        ArmFeatures::FP | ArmFeatures::NEON | ArmFeatures::AES | ArmFeatures::CRC32
+    );
+
+    let hwcaps = ArmFeatures::FP | ArmFeatures::NEON | ArmFeatures::SVE;
+    assert_eq!(
+        FeatureMismatch::between(hwcaps, info.features),
+        Some(FeatureMismatch {
+            hwcaps_only: ArmFeatures::SVE,
+            cpuinfo_only: ArmFeatures::AES | ArmFeatures::CRC32,
+        })
+    );
+    assert_eq!(FeatureMismatch::between(hwcaps, hwcaps), None);
+    assert_eq!(FeatureMismatch::between(ArmFeatures::empty(), hwcaps), None);
+}
+
+#[test]
+fn test_arm_version_inference() {
+    use cpuidrs::arm::version::{self, ArmVersion};
+    use cpuidrs::arm::ArmFeatures;
+    // Neoverse N1 hwcaps: Armv8.2 with LRCPC but no pointer authentication
+    let n1 = ArmFeatures::from_hwcaps(0x1011_9fff, 0, 0);
+    let report = version::infer(n1);
+    assert_eq!(report.version, Some(ArmVersion::V8_2));
+    assert_eq!(report.v9, None);
+    assert_eq!(
+        report.next[0],
+        (
+            ArmVersion::V8_3,
+            ArmFeatures::PACA | ArmFeatures::PACG | ArmFeatures::JSCVT | ArmFeatures::FCMA
+        )
+    );
+    assert_eq!(report.next[1].0, ArmVersion::V9_0);
+    assert!(report.next[1]
+        .1
+        .contains(ArmFeatures::SVE2 | ArmFeatures::BTI));
+
+    // Armv9.0 needs Armv8.5 plus SVE2, not Armv8.6
+    let v9 = ArmVersion::V8_5.required() | ArmFeatures::SVE | ArmFeatures::SVE2;
+    let report = version::infer(v9);
+    assert_eq!(
+        (report.v8, report.version),
+        (Some(ArmVersion::V8_5), Some(ArmVersion::V9_0))
+    );
+    assert_eq!(
+        report.next[1],
+        (
+            ArmVersion::V9_1,
+            ArmFeatures::BF16 | ArmFeatures::ECV | ArmFeatures::I8MM
+        )
+    );
+    assert!(ArmVersion::V9_2
+        .required()
+        .contains(ArmVersion::V8_7.required()));
+    assert_eq!(ArmVersion::V9_2.march(), "armv9.2-a");
+    assert_eq!(version::infer(ArmFeatures::empty()).version, None);
+}
+
+#[test]
+fn test_arm_cache_id_registers() {
+    use cpuidrs::arm::cacheregs::{Ctr, Dczid};
+    // Neoverse N1: 64-byte lines and granules, IDC and DIC set, PIPT L1I
+    let ctr = Ctr::from_raw(0x0000_0000_b444_c004);
+    assert_eq!((ctr.i_min_line, ctr.d_min_line), (64, 64));
+    assert!(ctr.idc && ctr.dic);
+    assert!(!ctr.requires_icache_invalidation() && !ctr.requires_dcache_clean());
+    assert_eq!((ctr.cwg, ctr.erg), (Some(64), Some(64)));
+    assert_eq!(ctr.l1_ip, 3);
+    // Cortex-A53: invalidation and cleaning both needed, ERG/CWG of 16 words
+    let a53 = Ctr::from_raw(0x8444_c004);
+    assert!(a53.requires_icache_invalidation() && a53.requires_dcache_clean());
+    assert_eq!(Ctr::from_raw(0x8000_0000).cwg, None);
+
+    assert_eq!(Dczid::from_raw(0x4).zva_block_size(), Some(64));
+    assert_eq!(Dczid::from_raw(0x14).zva_block_size(), None);
+}
+
+#[test]
+fn test_riscv_gather_from_captured_tree() {
+    let src = cpuidrs::sysfs::Source::memory([
+        (
+            "/proc/cpuinfo",
+            "processor\t: 0\nhart\t\t: 0\nisa\t\t: rv64imafdc\n",
+        ),
+        ("/sys/devices/system/cpu/cpu0/node0", ""),
+    ]);
+    let info = cpuidrs::riscv::gather_from(&src, 0);
+    assert_eq!(info.brand, "rv64imafdc");
+    assert_eq!(info.cores, 1);
+    assert_eq!(info.numa_node, Some(0));
+    assert!(info.profile().is_some());
+
+    // RV32 harts are never checked against the RV64 profiles
+    let src = cpuidrs::sysfs::Source::memory([(
+        "/proc/cpuinfo",
+        "processor\t: 0\nhart\t\t: 0\nisa\t\t: rv32imafdc_zicsr_zifencei_ziccif_ziccrse\n",
+    )]);
+    let rv32 = cpuidrs::riscv::gather_from(&src, 0);
+    assert_eq!(rv32.isa.as_ref().map(|i| i.xlen), Some(32));
+    assert!(rv32.profile().is_none());
+
+    // Without an ISA string the width is the one the crate was built for
+    let no_isa = cpuidrs::riscv::gather_from(
+        &cpuidrs::sysfs::Source::memory([("/proc/cpuinfo", "processor\t: 0\nhart\t\t: 0\n")]),
+        0,
+    );
+    let built = if cfg!(target_arch = "riscv64") {
+        Some(64)
+    } else if cfg!(target_arch = "riscv32") {
+        Some(32)
+    } else {
+        None
+    };
+    assert_eq!(no_isa.xlen(), built);
+    assert_eq!(no_isa.profile().is_some(), built == Some(64));
+}
+
+#[test]
+fn test_riscv_isa_string_parsing() {
+    use cpuidrs::riscv::isa::{Isa, IsaVersion};
+    use cpuidrs::riscv::RiscVFeatures;
+    let isa = Isa::parse("rv64imafdcv_zicsr_zifencei_zba1p0_zvl128b_sv39").unwrap();
+    assert_eq!((isa.xlen, isa.base), (64, 'i'));
+    let names: Vec<_> = isa.extensions.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["i", "m", "a", "f", "d", "c", "v", "zicsr", "zifencei", "zba", "zvl128b", "sv39"]
+    );
+    assert_eq!(
+        isa.extension("Zba").unwrap().version,
+        Some(IsaVersion { major: 1, minor: 0 })
+    );
+    assert_eq!(
+        RiscVFeatures::from_isa(&isa),
+        RiscVFeatures::I
+            | RiscVFeatures::M
+            | RiscVFeatures::A
+            | RiscVFeatures::F
+            | RiscVFeatures::D
+            | RiscVFeatures::C
+            | RiscVFeatures::V
+            | RiscVFeatures::ZICSR
+            | RiscVFeatures::ZIFENCEI
+            | RiscVFeatures::ZBA
+    );
+
+    // Versions on single letters, `p` as an extension after a version, and `g` expansion
+    let isa = Isa::parse("RV32G2P0CP_Zicsr2p0").unwrap();
+    assert!(isa.has("zifencei") && isa.has("c") && isa.has("p"));
+    assert_eq!(isa.extension("zicsr").unwrap().version, None);
+    let isa = Isa::parse("rv32e2p0m2").unwrap();
+    assert_eq!(isa.base, 'e');
+    assert_eq!(
+        isa.extension("m").unwrap().version,
+        Some(IsaVersion { major: 2, minor: 0 })
+    );
+    assert_eq!(isa.to_string(), "rv32e2p0m2p0");
+
+    for bad in [
+        "",
+        "rv64",
+        "rv48i",
+        "rv64q",
+        "x86_64",
+        "rv64i_z",
+        "rv64i_zi-csr",
+    ] {
+        assert_eq!(Isa::parse(bad), None, "{}", bad);
+    }
+}
+
+#[test]
+fn test_riscv_extension_catalog() {
+    use cpuidrs::riscv::isa::Isa;
+    use cpuidrs::riscv::RiscVFeatures as F;
+    // ISA line of a SpacemiT K1
+    let isa = Isa::parse(
+        "rv64imafdcv_zicbom_zicboz_zicntr_zicond_zicsr_zifencei_zihintpause_zihpm_\
+         zfh_zfhmin_zca_zcd_zba_zbb_zbc_zbs_zkt_zve32f_zve32x_zve64d_zve64f_zve64x_\
+         zvfh_zvfhmin_zvkt_sscofpmf_sstc_svinval_svnapot_svpbmt",
+    )
+    .unwrap();
+    let f = F::from_isa(&isa);
+    assert!(f.contains(F::V | F::ZICOND | F::ZBC | F::ZVFH | F::ZVE64D | F::SVPBMT | F::SSTC));
+    assert!(!f.intersects(F::B | F::ZACAS | F::ZVBB));
+    assert_eq!(f.iter().count(), isa.extensions.len());
+
+    // Shorthands expand into their components
+    assert_eq!(F::from_extension("B"), F::B | F::ZBA | F::ZBB | F::ZBS);
+    assert!(
+        F::from_extension("zvkng").contains(F::ZVKNED | F::ZVKNHB | F::ZVKB | F::ZVKT | F::ZVKG)
+    );
+    assert_eq!(F::from_extension("xunknown"), F::empty());
+    // misa: A, C, D, F, I, M, V
+    let misa = (1 << 0) | (1 << 2) | (1 << 3) | (1 << 5) | (1 << 8) | (1 << 12) | (1 << 21);
+    assert_eq!(
+        F::from_misa(misa),
+        F::I | F::M | F::A | F::F | F::D | F::C | F::V
+    );
+}
+
+#[test]
+fn test_riscv_hwprobe_pairs() {
+    use cpuidrs::riscv::hwprobe::{key, HwProbe, MisalignedPerf};
+    use cpuidrs::riscv::RiscVFeatures as F;
+    // Pairs as returned on a SpacemiT K1; key -1 marks a key the kernel does not know
+    let pairs = [
+        (key::MVENDORID, 0x710),
+        (key::MARCHID, 0x8000_0000_5800_0001),
+        (key::MIMPID, 0x1000_0000_4977_2200),
+        (key::BASE_BEHAVIOR, 1),
+        // FD C V ZBA ZBB ZBS ZICBOZ ZBC, ZKT, ZFH, ZFHMIN, ZVFH
+        (
+            key::IMA_EXT_0,
+            0xff | (1 << 16) | (1 << 27) | (1 << 28) | (1 << 30),
+        ),
+        (key::CPUPERF_0, 0),
+        (key::ZICBOZ_BLOCK_SIZE, 64),
+        (key::MISALIGNED_SCALAR_PERF, 2),
+        (-1, 0),
+    ];
+    let hw = HwProbe::from_pairs(&pairs);
+    assert_eq!(hw.mvendorid, Some(0x710));
+    assert_eq!(hw.marchid, Some(0x8000_0000_5800_0001));
+    assert_eq!(
+        hw.features,
+        F::I | F::M
+            | F::A
+            | F::F
+            | F::D
+            | F::C
+            | F::V
+            | F::ZBA
+            | F::ZBB
+            | F::ZBS
+            | F::ZICBOZ
+            | F::ZBC
+            | F::ZKT
+            | F::ZFH
+            | F::ZFHMIN
+            | F::ZVFH
+    );
+    // The dedicated key overrides the legacy CPUPERF_0 answer
+    assert_eq!(hw.misaligned_scalar, Some(MisalignedPerf::Slow));
+    assert_eq!(hw.misaligned_vector, None);
+    assert_eq!(hw.zicboz_block_size, Some(64));
+    assert_eq!(hw.time_csr_freq, None);
+    assert_eq!(HwProbe::from_pairs(&[]), HwProbe::default());
+
+    // Zicond has a bit this kernel left clear and Zicsr has none; both are kept
+    let isa = F::I | F::ZICOND | F::ZICSR | F::V;
+    assert_eq!(hw.merge_features(isa), hw.features | F::ZICOND | F::ZICSR);
+    assert_eq!(HwProbe::default().merge_features(isa), isa);
+    // An answered IMA_EXT_0 without V drops the ISA string's `v`
+    let no_v = HwProbe::from_pairs(&[(key::IMA_EXT_0, 0x3)]);
+    assert_eq!(
+        no_v.merge_features(isa),
+        F::F | F::D | F::C | F::I | F::ZICOND | F::ZICSR
+    );
+}
+
+#[test]
+fn test_riscv_vector_unit_detection() {
+    use cpuidrs::riscv::vector::{self, VectorInfo, VectorKind};
+    use cpuidrs::riscv::{gather_from, RiscVFeatures as F};
+    use cpuidrs::sysfs::Source;
+    // TH1520 (C910) vendor kernel: RVV 0.7.1 reported as plain `v`
+    let th1520 = Source::memory([
+        ("/proc/cpuinfo", "processor\t: 0\nisa\t\t: rv64imafdcvsu\n"),
+        (
+            "/sys/devices/system/cpu/cpu0/of_node/compatible",
+            "thead,c910\0riscv\0",
+        ),
+        (
+            "/sys/devices/system/cpu/cpu0/of_node/thead,vlenb",
+            "\0\0\0\x10",
+        ),
+    ]);
+    let info = gather_from(&th1520, 0);
+    assert_eq!(
+        info.vector,
+        Some(VectorInfo {
+            kind: VectorKind::XTheadVector,
+            vlen: Some(128),
+            elen: 64,
+        })
+    );
+    assert!(info.features.contains(F::XTHEADVECTOR));
+    assert!(!info.features.intersects(vector::rvv1_0_features()));
+
+    // SpacemiT K1: ratified RVV 1.0; VLEN comes from the vlenb CSR on the live system
+    let k1 = Source::memory([
+        (
+            "/proc/cpuinfo",
+            "processor\t: 0\nisa\t\t: rv64imafdcv_zve64d\n",
+        ),
+        (
+            "/sys/devices/system/cpu/cpu0/of_node/compatible",
+            "spacemit,x60\0riscv\0",
+        ),
+    ]);
+    let info = gather_from(&k1, 0);
+    let v = info.vector.unwrap();
+    assert_eq!((v.kind, v.vlen, v.elen), (VectorKind::Rvv1_0, None, 64));
+    assert!(info.features.contains(F::V) && !info.features.contains(F::XTHEADVECTOR));
+
+    // Embedded Zve32x subset, and an explicit version 0.7 in the ISA string
+    let zve32x = Source::memory([("/proc/cpuinfo", "processor\t: 0\nisa\t\t: rv32imc_zve32x\n")]);
+    assert_eq!(gather_from(&zve32x, 0).vector.unwrap().elen, 32);
+    let zve64d = Source::memory([(
+        "/proc/cpuinfo",
+        "processor\t: 0\nisa\t\t: rv64imafdc_zve64d\n",
+    )]);
+    assert_eq!(gather_from(&zve64d, 0).vector.unwrap().elen, 64);
+    let v0p7 = Source::memory([("/proc/cpuinfo", "processor\t: 0\nisa\t\t: rv64imafdcv0p7\n")]);
+    assert_eq!(
+        gather_from(&v0p7, 0).vector.map(|v| v.kind),
+        Some(VectorKind::XTheadVector)
+    );
+    let scalar = Source::memory([("/proc/cpuinfo", "processor\t: 0\nisa\t\t: rv64imafdc\n")]);
+    assert_eq!(gather_from(&scalar, 0).vector, None);
+}
+
+#[test]
+fn test_riscv_profile_compliance() {
+    use cpuidrs::riscv::isa::Isa;
+    use cpuidrs::riscv::profile::{self, RiscVProfile};
+    use cpuidrs::riscv::RiscVFeatures as F;
+    // JH7110 (U74) on a kernel that does not list the Zicc* guarantees
+    let u74 = F::from_isa(&Isa::parse("rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb").unwrap());
+    let report = profile::infer(u74);
+    assert_eq!(report.profile, Some(RiscVProfile::Rvi20U64));
+    assert!(!report.rvb23);
+    assert_eq!(
+        report.next[0],
+        (
+            RiscVProfile::Rva20U64,
+            F::ZICCIF | F::ZICCRSE | F::ZICCAMOA | F::ZICCLSM | F::ZA128RS
+        )
+    );
+    assert_eq!(report.next[1].0, RiscVProfile::Rvb23U64);
+
+    // RVA22 plus V; Za64rs stands in for Za128rs
+    let rva22v = RiscVProfile::Rva22U64.required() | F::V;
+    let report = profile::infer(rva22v);
+    assert_eq!(report.profile, Some(RiscVProfile::Rva22U64));
+    assert_eq!(report.profile.unwrap().to_string(), "RVA22U64");
+    assert!(RiscVProfile::Rva20U64.is_satisfied_by(rva22v));
+    assert_eq!(
+        report.next[0],
+        (
+            RiscVProfile::Rva23U64,
+            F::ZIHINTNTL
+                | F::ZICOND
+                | F::ZIMOP
+                | F::ZCMOP
+                | F::ZCB
+                | F::ZFA
+                | F::ZAWRS
+                | F::ZVFHMIN
+                | F::ZVBB
+                | F::ZVKT
+                | F::SUPM
+        )
+    );
+
+    // RVA23 implies RVB23
+    let report = profile::infer(RiscVProfile::Rva23U64.required());
+    assert_eq!(report.profile, Some(RiscVProfile::Rva23U64));
+    assert!(report.rvb23 && report.next.is_empty());
+    assert_eq!(profile::infer(F::empty()).profile, None);
+}
+
+#[test]
+fn test_riscv_machine_id_decoding() {
+    use cpuidrs::riscv::gather_from;
+    use cpuidrs::riscv::hwprobe::{key, HwProbe};
+    use cpuidrs::riscv::ids::MachineIds;
+    use cpuidrs::sysfs::Source;
+    // JH7110 on Linux 6.6
+    let jh7110 = Source::memory([(
+        "/proc/cpuinfo",
+        "processor\t: 1\nhart\t\t: 1\nisa\t\t: rv64imafdc_zicntr_zicsr_zifencei_zihpm_zba_zbb\n\
+         mmu\t\t: sv39\nuarch\t\t: sifive,u74-mc\nmvendorid\t: 0x489\n\
+         marchid\t\t: 0x8000000000000007\nmimpid\t\t: 0x4210427\n",
+    )]);
+    let info = gather_from(&jh7110, 1);
+    let ids = info.ids.unwrap();
+    assert_eq!((ids.jedec_bank(), ids.jedec_id()), (10, 0x09));
+    assert_eq!(ids.to_string(), "SiFive U74");
+    assert_eq!(
+        (info.vendor.as_str(), info.brand.as_str()),
+        ("SiFive", "U74")
+    );
+
+    // T-Head reports marchid 0 on every core; the device tree names it
+    let th1520 = Source::memory([
+        (
+            "/proc/cpuinfo",
+            "processor\t: 0\nisa\t\t: rv64imafdc\nmvendorid\t: 0x5b7\nmarchid\t\t: 0x0\n",
+        ),
+        (
+            "/sys/devices/system/cpu/cpu0/of_node/compatible",
+            "thead,c910\0riscv\0",
+        ),
+    ]);
+    let info = gather_from(&th1520, 0);
+    assert_eq!(format!("{} {}", info.vendor, info.brand), "T-Head C910");
+
+    // hwprobe IDs; open-source registry entries match any vendor
+    let hw = HwProbe::from_pairs(&[
+        (key::MVENDORID, 0x710),
+        (key::MARCHID, 0x8000_0000_5800_0001),
+        (key::MIMPID, 0x1000_0000_4977_2200),
+    ]);
+    assert_eq!(
+        MachineIds::from_hwprobe(&hw).unwrap().to_string(),
+        "SpacemiT X60"
+    );
+    let xiangshan = MachineIds {
+        mvendorid: 0,
+        marchid: 25,
+        mimpid: 0,
+    };
+    assert!(xiangshan.is_open_source_arch(64) && xiangshan.is_open_source_arch(32));
+    // Vendor-defined IDs set the top bit of an XLEN-wide marchid
+    let rv32_commercial = MachineIds {
+        mvendorid: 0x31e,
+        marchid: 0x8000_0025,
+        mimpid: 0,
+    };
+    assert!(!rv32_commercial.is_open_source_arch(32));
+    assert!(rv32_commercial.is_open_source_arch(64));
+    let rv64_commercial = MachineIds {
+        marchid: 0x8000_0000_0000_0007,
+        ..rv32_commercial
+    };
+    assert!(!rv64_commercial.is_open_source_arch(64));
+    assert_eq!(xiangshan.to_string(), "0x0 XiangShan");
+    assert_eq!(MachineIds::from_hwprobe(&HwProbe::default()), None);
+}
+
+#[test]
+fn test_arm_hwcap_decoding() {
+    use cpuidrs::arm::ArmFeatures;
+    // FP | ASIMD | AES | ATOMICS | ASIMDDP | SVE, plus GCS in the upper half
+    let hwcap = (1 << 0) | (1 << 1) | (1 << 3) | (1 << 8) | (1 << 20) | (1 << 22) | (1 << 32);
+    // SVE2 | I8MM | BF16 | SME | MOPS | POE
+    let hwcap2 = (1 << 1) | (1 << 13) | (1 << 14) | (1 << 23) | (1 << 43) | (1 << 63);
+    let f = ArmFeatures::from_hwcaps(hwcap, hwcap2, 1 << 1);
+    assert!(f.contains(
+        ArmFeatures::FP
+            | ArmFeatures::NEON
+            | ArmFeatures::AES
+            | ArmFeatures::ATOMICS
+            | ArmFeatures::ASIMDDP
+            | ArmFeatures::SVE
+            | ArmFeatures::GCS
+    ));
+    assert!(f.contains(
+        ArmFeatures::SVE2
+            | ArmFeatures::I8MM
+            | ArmFeatures::BF16
+            | ArmFeatures::SME
+            | ArmFeatures::MOPS
+            | ArmFeatures::POE
+            | ArmFeatures::MTE_STORE_ONLY
+    ));
+    assert!(!f.contains(ArmFeatures::PMULL));
+    assert!(!f.contains(ArmFeatures::MTE_FAR));
+    assert_eq!(f.iter().count(), 14);
+}
+
+#[test]
+fn test_arm32_hwcap_decoding() {
+    use cpuidrs::arm::ArmFeatures;
+    // Raspberry Pi 3 in 32-bit mode: half thumb fastmult vfp edsp neon vfpv3 tls vfpv4
+    // idiva idivt vfpd32 lpae evtstrm, plus crc32 in AT_HWCAP2
+    let f = ArmFeatures::from_hwcaps32(0x003f_b0d6, 1 << 4);
+    assert!(f.contains(
+        ArmFeatures::HALF
+            | ArmFeatures::THUMB
+            | ArmFeatures::FASTMULT
+            | ArmFeatures::VFP
+            | ArmFeatures::NEON
+            | ArmFeatures::VFPV3
+            | ArmFeatures::VFPV4
+            | ArmFeatures::IDIVA
+            | ArmFeatures::IDIVT
+            | ArmFeatures::VFPD32
+            | ArmFeatures::LPAE
+            | ArmFeatures::EVTSTRM
+            | ArmFeatures::CRC32
+    ));
+    // Bit 3 of AT_HWCAP is AES on aarch64 but 26BIT on 32-bit ARM
+    assert!(!f.contains(ArmFeatures::AES));
+    assert!(!f.contains(ArmFeatures::SWP));
+}